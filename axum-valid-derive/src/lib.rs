@@ -0,0 +1,401 @@
+//! # axum-valid-derive
+//!
+//! Derive macros for implementing the extractor traits of [axum-valid](https://docs.rs/axum-valid)
+//! on custom extractors.
+//!
+//! These macros are re-exported by `axum-valid` when its `derive` feature is enabled,
+//! prefer using them from there.
+//!
+//! ## Inner field
+//!
+//! Every derive points the trait at a single field of the extractor:
+//!
+//! * For structs with exactly one field (e.g. newtypes), that field is used.
+//! * Otherwise, the field must be marked with `#[axum_valid(inner)]`.
+//!
+//! ## Derives
+//!
+//! | Derive              | Implements                                                         | Requirements                                                     |
+//! |---------------------|--------------------------------------------------------------------|------------------------------------------------------------------|
+//! | `HasValidate`       | `axum_valid::HasValidate`                                          |                                                                  |
+//! | `HasValidateArgs`   | `axum_valid::HasValidateArgs` (`validator` feature)                |                                                                  |
+//! | `HasModify`         | `axum_valid::HasModify` (`validify` feature)                       |                                                                  |
+//! | `PayloadExtractor`  | `axum_valid::PayloadExtractor` (`validify` feature)                |                                                                  |
+//! | `HasValidify`       | `axum_valid::HasValidify` (`validify` feature)                     | The inner field's type is a type parameter, other fields `Default` |
+//! | `Normalize`         | `axum_valid::normalize::Normalize` (`normalize` feature)           | See below                                                        |
+//!
+//! ## Normalize
+//!
+//! Unlike the other derives, `Normalize` is derived for the data rather than the extractor.
+//! Each field lists the operations to apply with `#[normalize(...)]`, in order:
+//!
+//! * `trim`, `lowercase`, `collapse_whitespace`, `nfc` and `strip_control` call the function of the same name
+//!   in `axum_valid::normalize`, the field's type must implement `axum_valid::normalize::TextField`.
+//! * `sanitize_html` and `strip_html` work the same way, and require the `sanitize` feature of `axum-valid`.
+//! * `nested` normalizes a field whose type implements `axum_valid::normalize::Normalize` itself.
+//!
+//! Fields without the attribute are left unchanged.
+//!
+
+#![deny(unsafe_code, missing_docs, clippy::unwrap_used)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericArgument,
+    GenericParam, Generics, Ident, Index, Lifetime, LifetimeParam, Member, Type,
+};
+
+const ATTRIBUTE: &str = "axum_valid";
+const NORMALIZE_ATTRIBUTE: &str = "normalize";
+const NORMALIZE_OPERATIONS: [&str; 7] = [
+    "trim",
+    "lowercase",
+    "collapse_whitespace",
+    "nfc",
+    "strip_control",
+    "sanitize_html",
+    "strip_html",
+];
+
+/// Derives `axum_valid::HasValidate` for a custom extractor.
+#[proc_macro_derive(HasValidate, attributes(axum_valid))]
+pub fn derive_has_validate(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), has_validate)
+}
+
+/// Derives `axum_valid::HasValidateArgs` for a custom extractor.
+#[proc_macro_derive(HasValidateArgs, attributes(axum_valid))]
+pub fn derive_has_validate_args(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), has_validate_args)
+}
+
+/// Derives `axum_valid::HasModify` for a custom extractor.
+#[proc_macro_derive(HasModify, attributes(axum_valid))]
+pub fn derive_has_modify(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), has_modify)
+}
+
+/// Derives `axum_valid::PayloadExtractor` for a custom extractor.
+#[proc_macro_derive(PayloadExtractor, attributes(axum_valid))]
+pub fn derive_payload_extractor(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), payload_extractor)
+}
+
+/// Derives `axum_valid::HasValidify` for a custom extractor.
+///
+/// The type of the inner field must be one of the extractor's type parameters,
+/// and all other fields must implement `Default`.
+#[proc_macro_derive(HasValidify, attributes(axum_valid))]
+pub fn derive_has_validify(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), has_validify)
+}
+
+/// Derives `axum_valid::normalize::Normalize` for the data, from the `#[normalize(...)]` attributes of its fields.
+#[proc_macro_derive(Normalize, attributes(normalize))]
+pub fn derive_normalize(input: TokenStream) -> TokenStream {
+    normalize(&parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(
+    input: DeriveInput,
+    f: fn(&DeriveInput, &InnerField) -> syn::Result<TokenStream2>,
+) -> TokenStream {
+    InnerField::find(&input)
+        .and_then(|inner| f(&input, &inner))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The field of the extractor that holds the data to be validated.
+struct InnerField {
+    member: Member,
+    ty: Type,
+    fields: Fields,
+}
+
+impl InnerField {
+    fn find(input: &DeriveInput) -> syn::Result<Self> {
+        let Data::Struct(data) = &input.data else {
+            return Err(Error::new(
+                input.span(),
+                "axum-valid derives only support structs",
+            ));
+        };
+
+        let mut marked = None;
+        for (index, field) in data.fields.iter().enumerate() {
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident(ATTRIBUTE)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("inner") {
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `inner`"))
+                    }
+                })?;
+                if marked.is_some() {
+                    return Err(Error::new(
+                        attr.span(),
+                        "only one field can be marked with `#[axum_valid(inner)]`",
+                    ));
+                }
+                marked = Some((index, field));
+            }
+        }
+
+        let (index, field) = match marked {
+            Some(marked) => marked,
+            None if data.fields.len() == 1 => data
+                .fields
+                .iter()
+                .enumerate()
+                .next()
+                .ok_or_else(|| Error::new(input.span(), "expected a field"))?,
+            None => {
+                return Err(Error::new(
+                    input.span(),
+                    "mark the field to validate with `#[axum_valid(inner)]`",
+                ))
+            }
+        };
+
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+
+        Ok(InnerField {
+            member,
+            ty: field.ty.clone(),
+            fields: data.fields.clone(),
+        })
+    }
+
+    /// Builds an expression of the extractor type using `value` for the inner field,
+    /// and `Default::default()` for the others.
+    fn construct(&self, value: TokenStream2) -> TokenStream2 {
+        let mut value = Some(value);
+        let inits = self.fields.iter().enumerate().map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(index)),
+            };
+            let init = if member == self.member {
+                value.take().unwrap_or_default()
+            } else {
+                quote!(::core::default::Default::default())
+            };
+            quote!(#member: #init)
+        });
+        quote!(Self { #(#inits),* })
+    }
+}
+
+fn has_validate(input: &DeriveInput, inner: &InnerField) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let InnerField { member, ty, .. } = inner;
+    Ok(quote! {
+        impl #impl_generics ::axum_valid::HasValidate for #name #ty_generics #where_clause {
+            type Validate = #ty;
+            fn get_validate(&self) -> &Self::Validate {
+                &self.#member
+            }
+        }
+    })
+}
+
+fn has_validate_args(input: &DeriveInput, inner: &InnerField) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let InnerField { member, ty, .. } = inner;
+    let lifetime = Lifetime::new("'__v", Span::call_site());
+    let mut generics = input.generics.clone();
+    generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+    );
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::axum_valid::__private::validator::ValidateArgs<#lifetime>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::axum_valid::HasValidateArgs<#lifetime> for #name #ty_generics #where_clause {
+            type ValidateArgs = #ty;
+            fn get_validate_args(&self) -> &Self::ValidateArgs {
+                &self.#member
+            }
+        }
+    })
+}
+
+fn has_modify(input: &DeriveInput, inner: &InnerField) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let InnerField { member, ty, .. } = inner;
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::axum_valid::__private::validify::Modify));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::axum_valid::HasModify for #name #ty_generics #where_clause {
+            type Modify = #ty;
+            fn get_modify(&mut self) -> &mut Self::Modify {
+                &mut self.#member
+            }
+        }
+    })
+}
+
+fn payload_extractor(input: &DeriveInput, inner: &InnerField) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let InnerField { member, ty, .. } = inner;
+    Ok(quote! {
+        impl #impl_generics ::axum_valid::PayloadExtractor for #name #ty_generics #where_clause {
+            type Payload = #ty;
+            fn get_payload(self) -> Self::Payload {
+                self.#member
+            }
+        }
+    })
+}
+
+fn has_validify(input: &DeriveInput, inner: &InnerField) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let param = type_param(&input.generics, &inner.ty).ok_or_else(|| {
+        Error::new(
+            inner.ty.span(),
+            "`HasValidify` requires the inner field's type to be a type parameter of the extractor",
+        )
+    })?;
+
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.push(parse_quote!(
+        #param: ::axum_valid::__private::validify::Validify
+            + ::axum_valid::__private::validify::ValidifyPayload
+    ));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let payload_args = input.generics.params.iter().map(|p| match p {
+        GenericParam::Type(t) if &t.ident == param => GenericArgument::Type(parse_quote!(
+            <#param as ::axum_valid::__private::validify::ValidifyPayload>::Payload
+        )),
+        GenericParam::Type(t) => {
+            let ident = &t.ident;
+            GenericArgument::Type(parse_quote!(#ident))
+        }
+        GenericParam::Lifetime(l) => GenericArgument::Lifetime(l.lifetime.clone()),
+        GenericParam::Const(c) => {
+            let ident = &c.ident;
+            GenericArgument::Const(parse_quote!(#ident))
+        }
+    });
+    let construct = inner.construct(quote!(v));
+
+    Ok(quote! {
+        impl #impl_generics ::axum_valid::HasValidify for #name #ty_generics #where_clause {
+            type Validify = #param;
+            type PayloadExtractor = #name<#(#payload_args),*>;
+            fn from_validify(v: Self::Validify) -> Self {
+                #construct
+            }
+        }
+    })
+}
+
+fn normalize(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "`Normalize` can only be derived for structs",
+        ));
+    };
+
+    let mut generics = input.generics.clone();
+    let mut statements = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+        let ty = &field.ty;
+        let mut text = false;
+        let mut nested = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident(NORMALIZE_ATTRIBUTE))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("nested") {
+                    nested = true;
+                    statements.push(quote! {
+                        ::axum_valid::normalize::Normalize::normalize(&mut self.#member);
+                    });
+                    return Ok(());
+                }
+                match NORMALIZE_OPERATIONS
+                    .iter()
+                    .find(|op| meta.path.is_ident(op))
+                {
+                    Some(op) => {
+                        text = true;
+                        let op = Ident::new(op, meta.path.span());
+                        statements.push(quote! {
+                            ::axum_valid::normalize::#op(&mut self.#member);
+                        });
+                        Ok(())
+                    }
+                    None => Err(meta.error(format!(
+                        "expected one of `nested`, `{}`",
+                        NORMALIZE_OPERATIONS.join("`, `")
+                    ))),
+                }
+            })?;
+        }
+        if text {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: ::axum_valid::normalize::TextField));
+        }
+        if nested {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: ::axum_valid::normalize::Normalize));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::axum_valid::normalize::Normalize for #name #ty_generics #where_clause {
+            fn normalize(&mut self) {
+                #(#statements)*
+            }
+        }
+    })
+}
+
+/// Returns the type parameter of `generics` that `ty` refers to, if any.
+fn type_param<'g>(generics: &'g Generics, ty: &Type) -> Option<&'g Ident> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let ident = path.path.get_ident()?;
+    generics
+        .type_params()
+        .map(|p| &p.ident)
+        .find(|p| *p == ident)
+}
@@ -0,0 +1,338 @@
+//! # Validation with plain functions
+//!
+//! For small handlers, deriving `Validate` with one of the validation backends can be more than needed.
+//!
+//! `Checked<E, R>` validates the data extracted by `E` with the rule type `R`, which implements the [`Validator`]
+//! trait with a plain function. It works with every extractor implementing `HasValidate`, and requires none of the
+//! `validator`, `garde` or `validify` features.
+//!
+//! The error returned by the rule is rejected with the standard validation rejection, so it must implement
+//! `Display`, or `Serialize` with the `into_json` feature.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "json")]
+//! mod json_example {
+//!     use axum::routing::post;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::{Checked, Validator};
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/json", post(handler))
+//!     }
+//!
+//!     async fn handler(Checked(Json(range), _): Checked<Json<Range>, ValidRange>) {
+//!         assert!(range.start <= range.end);
+//!     }
+//!
+//!     #[derive(Deserialize)]
+//!     pub struct Range {
+//!         pub start: u32,
+//!         pub end: u32,
+//!     }
+//!
+//!     pub struct ValidRange;
+//!
+//!     impl Validator<Range> for ValidRange {
+//!         type Error = String;
+//!
+//!         fn validate(range: &Range) -> Result<(), Self::Error> {
+//!             if range.start <= range.end {
+//!                 Ok(())
+//!             } else {
+//!                 Err(format!("start {} is after end {}", range.start, range.end))
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "json")]
+//! #     let router = router.nest("/json", json_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::{HasValidate, ValidationRejection};
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::response::IntoResponse;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// Trait for rule types validating data of type `T` with a plain function.
+///
+/// The rule type is only used as a marker, and is never constructed.
+pub trait Validator<T: ?Sized> {
+    /// Error returned when the data is invalid
+    type Error;
+    /// Validate the data
+    fn validate(value: &T) -> Result<(), Self::Error>;
+}
+
+/// # `Checked` data extractor
+///
+/// `Checked` validates the inner data with the rule type `R`, see [`Validator`].
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Checked<E, R>(pub E, pub PhantomData<R>);
+
+impl<E, R> Deref for Checked<E, R> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E, R> DerefMut for Checked<E, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display, R> Display for Checked<T, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E, R> Checked<E, R> {
+    /// Consumes the `Checked` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T, R> aide::OperationInput for Checked<T, R>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+/// `CheckedRejection` is returned when the `Checked` extractor fails.
+///
+/// `V` is the error type of the rule, and `E` is the rejection type of the inner extractor.
+///
+pub type CheckedRejection<V, E> = ValidationRejection<V, E>;
+
+impl<State, Extractor, Rule> FromRequest<State> for Checked<Extractor, Rule>
+where
+    State: Send + Sync,
+    Extractor: HasValidate + FromRequest<State>,
+    Rule: Validator<Extractor::Validate>,
+    CheckedRejection<Rule::Error, <Extractor as FromRequest<State>>::Rejection>: IntoResponse,
+{
+    type Rejection = CheckedRejection<Rule::Error, <Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(CheckedRejection::Inner)?;
+        Rule::validate(inner.get_validate()).map_err(CheckedRejection::Valid)?;
+        Ok(Checked(inner, PhantomData))
+    }
+}
+
+impl<State, Extractor, Rule> FromRequestParts<State> for Checked<Extractor, Rule>
+where
+    State: Send + Sync,
+    Extractor: HasValidate + FromRequestParts<State>,
+    Rule: Validator<Extractor::Validate>,
+    CheckedRejection<Rule::Error, <Extractor as FromRequestParts<State>>::Rejection>: IntoResponse,
+{
+    type Rejection =
+        CheckedRejection<Rule::Error, <Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(CheckedRejection::Inner)?;
+        Rule::validate(inner.get_validate()).map_err(CheckedRejection::Valid)?;
+        Ok(Checked(inner, PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::io;
+
+    const CHECKED: &str = "checked";
+
+    struct NotEmpty;
+
+    impl Validator<String> for NotEmpty {
+        type Error = io::Error;
+
+        fn validate(value: &String) -> Result<(), Self::Error> {
+            if value.is_empty() {
+                Err(io::Error::other(CHECKED))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn checked_deref_deref_mut_into_inner() {
+        let mut inner = String::from(CHECKED);
+        let mut v = Checked::<_, NotEmpty>(inner.clone(), PhantomData);
+        assert_eq!(&inner, v.deref());
+        inner.push_str(CHECKED);
+        v.deref_mut().push_str(CHECKED);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[cfg(feature = "query")]
+    #[tokio::test]
+    async fn checked_query() {
+        use axum::extract::Query;
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize)]
+        struct Range {
+            start: u32,
+            end: u32,
+        }
+
+        struct ValidRange;
+
+        impl Validator<Range> for ValidRange {
+            type Error = String;
+
+            fn validate(range: &Range) -> Result<(), Self::Error> {
+                if range.start <= range.end {
+                    Ok(())
+                } else {
+                    Err(String::from(CHECKED))
+                }
+            }
+        }
+
+        async fn extract(
+            uri: &'static str,
+        ) -> Result<Range, CheckedRejection<String, axum::extract::rejection::QueryRejection>>
+        {
+            let (mut parts, _) = Request::builder()
+                .uri(uri)
+                .body(())
+                .expect("build request")
+                .into_parts();
+            Checked::<Query<Range>, ValidRange>::from_request_parts(&mut parts, &())
+                .await
+                .map(|Checked(Query(range), _)| range)
+        }
+
+        let range = extract("/?start=1&end=2").await.expect("valid range");
+        assert_eq!((range.start, range.end), (1, 2));
+        assert!(matches!(
+            extract("/?start=2&end=1").await,
+            Err(CheckedRejection::Valid(error)) if error == CHECKED
+        ));
+        assert!(matches!(
+            extract("/?start=2").await,
+            Err(CheckedRejection::Inner(_))
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn checked_json() -> anyhow::Result<()> {
+        use crate::VALIDATION_ERROR_STATUS;
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use reqwest::Client;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+
+        struct ShortList;
+
+        impl Validator<Vec<String>> for ShortList {
+            type Error = String;
+
+            fn validate(list: &Vec<String>) -> Result<(), Self::Error> {
+                if list.len() <= 2 {
+                    Ok(())
+                } else {
+                    Err(format!("{} items, expected at most 2", list.len()))
+                }
+            }
+        }
+
+        async fn handler(Checked(Json(list), _): Checked<Json<Vec<String>>, ShortList>) -> String {
+            list.join(",")
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        let response = client.post(&url).json(&["a", "b"]).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "a,b");
+
+        let response = client.post(&url).json(&["a", "b", "c"]).send().await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        #[cfg(not(feature = "into_json"))]
+        assert_eq!(response.text().await?, "3 items, expected at most 2");
+
+        let response = client.post(&url).body("[]").send().await?;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        Ok(())
+    }
+
+    #[test]
+    fn display_error() {
+        // CheckedRejection::Valid Display
+        let vr = CheckedRejection::<String, String>::Valid(String::from(CHECKED));
+        assert_eq!(vr.to_string(), CHECKED);
+
+        // CheckedRejection::Inner Display
+        let inner = String::from(CHECKED);
+        let vr = CheckedRejection::<String, String>::Inner(inner.clone());
+        assert_eq!(inner.to_string(), vr.to_string());
+
+        // CheckedRejection::Valid Error
+        let vr = CheckedRejection::<io::Error, io::Error>::Valid(
+            NotEmpty::validate(&String::new()).expect_err("empty string"),
+        );
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<io::Error>().is_some())
+        );
+
+        // CheckedRejection::Inner Error
+        let vr = CheckedRejection::<io::Error, io::Error>::Inner(io::Error::other(CHECKED));
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<io::Error>().is_some())
+        );
+    }
+}
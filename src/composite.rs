@@ -0,0 +1,549 @@
+//! # Validation with two backends at once
+//!
+//! ## Feature
+//!
+//! Enable the `validator` and `garde` features to use `ValidGarde<E>`,
+//! or the `validator` and `validify` features to use `ValidValidated<E>`.
+//!
+//! ## Usage
+//!
+//! When migrating from `validator` to another backend, both rule sets can be checked on the same data.
+//! The data is extracted once, validated by both backends, and the failures of both are combined into a single
+//! [`CompositeErrors`], keyed by backend name when serialized with the `into_json` feature.
+//!
+//! 1. Implement both `validator::Validate` and `garde::Validate` (or `validify::Validate`) for your data type `T`.
+//! 2. In your handler function, use `ValidGarde<E>` (or `ValidValidated<E>`) as some parameter's type.
+//!
+//! As with `Garde<E>`, the garde context is taken from the state, meaning implementing `FromRef<StateType>` for your
+//! context type, or for `()` if your router uses a state.
+//!
+//! Note that `validator` and `validify` both use the `validate` attribute, so only one of them can be derived for the
+//! same type, and the other must be implemented by hand.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::routing::post;
+//!     use axum::{Json, Router};
+//!     use axum_valid::ValidGarde;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/json", post(handler))
+//!     }
+//!
+//!     async fn handler(ValidGarde(Json(parameter)): ValidGarde<Json<Parameter>>) {
+//!         assert!(validator::Validate::validate(&parameter).is_ok());
+//!         assert!(garde::Validate::validate(&parameter).is_ok());
+//!     }
+//!
+//!     #[derive(Deserialize, validator::Validate, garde::Validate)]
+//!     pub struct Parameter {
+//!         // Old rules
+//!         #[validate(range(min = 5, max = 10))]
+//!         // New rules
+//!         #[garde(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::{HasValidate, ValidationRejection};
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// Validation errors of several backends, `None` for backends that passed or weren't run.
+#[derive(Debug, Default)]
+pub struct CompositeErrors {
+    /// Errors of `validator`
+    pub validator: Option<validator::ValidationErrors>,
+    /// Errors of `garde`
+    #[cfg(feature = "garde")]
+    pub garde: Option<garde::Report>,
+    /// Errors of `validify`
+    #[cfg(feature = "validify")]
+    pub validify: Option<validify::ValidationErrors>,
+}
+
+impl CompositeErrors {
+    fn with_validator(mut self, result: Result<(), validator::ValidationErrors>) -> Self {
+        self.validator = result.err();
+        self
+    }
+
+    #[cfg(feature = "garde")]
+    fn with_garde(mut self, result: Result<(), garde::Report>) -> Self {
+        self.garde = result.err();
+        self
+    }
+
+    #[cfg(feature = "validify")]
+    fn with_validify(mut self, result: Result<(), validify::ValidationErrors>) -> Self {
+        self.validify = result.err();
+        self
+    }
+
+    /// Returns `true` if no backend reported errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors().is_empty()
+    }
+
+    fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    fn errors(&self) -> Vec<&(dyn std::error::Error + 'static)> {
+        let mut errors: Vec<&(dyn std::error::Error + 'static)> = Vec::new();
+        if let Some(error) = &self.validator {
+            errors.push(error);
+        }
+        #[cfg(feature = "garde")]
+        if let Some(error) = &self.garde {
+            errors.push(error);
+        }
+        #[cfg(feature = "validify")]
+        if let Some(error) = &self.validify {
+            errors.push(error);
+        }
+        errors
+    }
+}
+
+impl Display for CompositeErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, error) in self.errors().into_iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CompositeErrors {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.errors().into_iter().next()
+    }
+}
+
+#[cfg(feature = "into_json")]
+impl serde::Serialize for CompositeErrors {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(errors) = &self.validator {
+            map.serialize_entry("validator", errors)?;
+        }
+        #[cfg(feature = "garde")]
+        if let Some(errors) = &self.garde {
+            map.serialize_entry("garde", errors)?;
+        }
+        #[cfg(feature = "validify")]
+        if let Some(errors) = &self.validify {
+            map.serialize_entry("validify", errors)?;
+        }
+        map.end()
+    }
+}
+
+/// `CompositeRejection` is returned when the `ValidGarde` or `ValidValidated` extractor fails.
+///
+pub type CompositeRejection<E> = ValidationRejection<CompositeErrors, E>;
+
+impl<E> From<CompositeErrors> for CompositeRejection<E> {
+    fn from(value: CompositeErrors) -> Self {
+        Self::Valid(value)
+    }
+}
+
+/// # `ValidGarde` data extractor
+///
+/// `ValidGarde` validates the inner data with both `validator` and `garde`, see the [module documentation](self).
+///
+#[cfg(feature = "garde")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidGarde<E>(pub E);
+
+/// # `ValidValidated` data extractor
+///
+/// `ValidValidated` validates the inner data with both `validator` and `validify`,
+/// see the [module documentation](self).
+///
+#[cfg(feature = "validify")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidValidated<E>(pub E);
+
+macro_rules! impl_composite {
+    ($name:ident) => {
+        impl<E> Deref for $name<E> {
+            type Target = E;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<E> DerefMut for $name<E> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<T: Display> Display for $name<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl<E> $name<E> {
+            #[doc = concat!("Consumes the `", stringify!($name), "` and returns the validated data within.")]
+            ///
+            /// This returns the `E` type which represents the data that has been
+            /// successfully validated.
+            pub fn into_inner(self) -> E {
+                self.0
+            }
+        }
+
+        #[cfg(feature = "aide")]
+        impl<T> aide::OperationInput for $name<T>
+        where
+            T: aide::OperationInput,
+        {
+            fn operation_input(
+                ctx: &mut aide::generate::GenContext,
+                operation: &mut aide::openapi::Operation,
+            ) {
+                T::operation_input(ctx, operation);
+            }
+        }
+    };
+}
+
+#[cfg(feature = "garde")]
+impl_composite!(ValidGarde);
+#[cfg(feature = "validify")]
+impl_composite!(ValidValidated);
+
+#[cfg(feature = "garde")]
+impl<State, Extractor, Context> FromRequest<State> for ValidGarde<Extractor>
+where
+    State: Send + Sync,
+    Context: Send + Sync + axum::extract::FromRef<State>,
+    Extractor: HasValidate + FromRequest<State>,
+    Extractor::Validate: validator::Validate + garde::Validate<Context = Context>,
+{
+    type Rejection = CompositeRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let context = Context::from_ref(state);
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(CompositeRejection::Inner)?;
+        let data = inner.get_validate();
+        CompositeErrors::default()
+            .with_validator(validator::Validate::validate(data))
+            .with_garde(garde::Validate::validate_with(data, &context))
+            .into_result()?;
+        Ok(ValidGarde(inner))
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<State, Extractor, Context> FromRequestParts<State> for ValidGarde<Extractor>
+where
+    State: Send + Sync,
+    Context: Send + Sync + axum::extract::FromRef<State>,
+    Extractor: HasValidate + FromRequestParts<State>,
+    Extractor::Validate: validator::Validate + garde::Validate<Context = Context>,
+{
+    type Rejection = CompositeRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let context = Context::from_ref(state);
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(CompositeRejection::Inner)?;
+        let data = inner.get_validate();
+        CompositeErrors::default()
+            .with_validator(validator::Validate::validate(data))
+            .with_garde(garde::Validate::validate_with(data, &context))
+            .into_result()?;
+        Ok(ValidGarde(inner))
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<State, Extractor> FromRequest<State> for ValidValidated<Extractor>
+where
+    State: Send + Sync,
+    Extractor: HasValidate + FromRequest<State>,
+    Extractor::Validate: validator::Validate + validify::Validate,
+{
+    type Rejection = CompositeRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(CompositeRejection::Inner)?;
+        let data = inner.get_validate();
+        CompositeErrors::default()
+            .with_validator(validator::Validate::validate(data))
+            .with_validify(validify::Validate::validate(data))
+            .into_result()?;
+        Ok(ValidValidated(inner))
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<State, Extractor> FromRequestParts<State> for ValidValidated<Extractor>
+where
+    State: Send + Sync,
+    Extractor: HasValidate + FromRequestParts<State>,
+    Extractor::Validate: validator::Validate + validify::Validate,
+{
+    type Rejection = CompositeRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(CompositeRejection::Inner)?;
+        let data = inner.get_validate();
+        CompositeErrors::default()
+            .with_validator(validator::Validate::validate(data))
+            .with_validify(validify::Validate::validate(data))
+            .into_result()?;
+        Ok(ValidValidated(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::io;
+
+    const COMPOSITE: &str = "composite";
+
+    fn validator_errors() -> validator::ValidationErrors {
+        let mut errors = validator::ValidationErrors::new();
+        errors.add("v0", validator::ValidationError::new(COMPOSITE));
+        errors
+    }
+
+    #[test]
+    fn composite_errors() {
+        let errors = CompositeErrors::default().with_validator(Ok(()));
+        assert!(errors.is_empty());
+        assert!(errors.into_result().is_ok());
+
+        let errors = CompositeErrors::default().with_validator(Err(validator_errors()));
+        assert!(!errors.is_empty());
+        assert_eq!(errors.to_string(), validator_errors().to_string());
+        assert!(matches!(
+            errors.source(),
+            Some(source) if source.downcast_ref::<validator::ValidationErrors>().is_some()
+        ));
+    }
+
+    #[cfg(feature = "garde")]
+    #[test]
+    fn valid_garde_deref_deref_mut_into_inner() {
+        let mut inner = String::from(COMPOSITE);
+        let mut v = ValidGarde(inner.clone());
+        assert_eq!(&inner, v.deref());
+        inner.push_str(COMPOSITE);
+        v.deref_mut().push_str(COMPOSITE);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[cfg(all(feature = "garde", feature = "json"))]
+    #[tokio::test]
+    async fn valid_garde() -> anyhow::Result<()> {
+        use crate::VALIDATION_ERROR_STATUS;
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use reqwest::Client;
+        use serde::Deserialize;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+
+        #[derive(Debug, Deserialize, validator::Validate, garde::Validate)]
+        struct Parameters {
+            // Only checked by validator
+            #[validate(range(max = 10))]
+            #[garde(skip)]
+            v0: i32,
+            // Only checked by garde
+            #[garde(length(max = 4))]
+            v1: String,
+        }
+
+        async fn handler(ValidGarde(Json(parameters)): ValidGarde<Json<Parameters>>) -> String {
+            parameters.v1
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "v0": 1, "v1": "abc" }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "abc");
+
+        for (v0, v1, validator, garde) in [
+            (11, "abc", true, false),
+            (1, "abcde", false, true),
+            (11, "abcde", true, true),
+        ] {
+            let response = client
+                .post(&url)
+                .json(&serde_json::json!({ "v0": v0, "v1": v1 }))
+                .send()
+                .await?;
+            assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+            #[cfg(feature = "into_json")]
+            {
+                let errors = response.json::<serde_json::Value>().await?;
+                assert_eq!(errors.get("validator").is_some(), validator);
+                assert_eq!(errors.get("garde").is_some(), garde);
+            }
+            #[cfg(not(feature = "into_json"))]
+            {
+                let errors = response.text().await?;
+                assert_eq!(errors.contains("v0"), validator);
+                assert_eq!(errors.contains("v1"), garde);
+            }
+        }
+
+        let response = client.post(&url).body("{}").send().await?;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "validify", feature = "query"))]
+    #[tokio::test]
+    async fn valid_validated() {
+        use axum::extract::Query;
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize, validator::Validate)]
+        struct Parameters {
+            #[validate(range(max = 10))]
+            v0: i32,
+            v1: String,
+        }
+
+        impl validify::Validate for Parameters {
+            fn validate(&self) -> Result<(), validify::ValidationErrors> {
+                if self.v1.len() <= 4 {
+                    return Ok(());
+                }
+                let mut errors = validify::ValidationErrors::new();
+                errors.add(validify::ValidationError::new_field_named("v1", "length"));
+                Err(errors)
+            }
+        }
+
+        async fn extract(
+            uri: &'static str,
+        ) -> Result<Parameters, CompositeRejection<axum::extract::rejection::QueryRejection>>
+        {
+            let (mut parts, _) = Request::builder()
+                .uri(uri)
+                .body(())
+                .expect("build request")
+                .into_parts();
+            ValidValidated::<Query<Parameters>>::from_request_parts(&mut parts, &())
+                .await
+                .map(|ValidValidated(Query(parameters))| parameters)
+        }
+
+        let parameters = extract("/?v0=1&v1=abc").await.expect("valid parameters");
+        assert_eq!(parameters.v1, "abc");
+        assert!(matches!(
+            extract("/?v0=11&v1=abcde").await,
+            Err(CompositeRejection::Valid(CompositeErrors {
+                validator: Some(_),
+                validify: Some(_),
+                ..
+            }))
+        ));
+        assert!(matches!(
+            extract("/?v0=1&v1=abcde").await,
+            Err(CompositeRejection::Valid(CompositeErrors {
+                validator: None,
+                validify: Some(_),
+                ..
+            }))
+        ));
+        assert!(matches!(
+            extract("/?v0=1").await,
+            Err(CompositeRejection::Inner(_))
+        ));
+    }
+
+    #[test]
+    fn display_error() {
+        // CompositeRejection::Valid Display
+        let errors = CompositeErrors::default().with_validator(Err(validator_errors()));
+        let s = errors.to_string();
+        let vr = CompositeRejection::<String>::Valid(errors);
+        assert_eq!(vr.to_string(), s);
+
+        // CompositeRejection::Inner Display
+        let inner = String::from(COMPOSITE);
+        let vr = CompositeRejection::<String>::Inner(inner.clone());
+        assert_eq!(inner.to_string(), vr.to_string());
+
+        // CompositeRejection::Valid Error
+        let vr = CompositeRejection::<io::Error>::Valid(
+            CompositeErrors::default().with_validator(Err(validator_errors())),
+        );
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<CompositeErrors>().is_some())
+        );
+
+        // CompositeRejection::Inner Error
+        let vr = CompositeRejection::<io::Error>::Inner(io::Error::other(COMPOSITE));
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<io::Error>().is_some())
+        );
+    }
+}
@@ -0,0 +1,549 @@
+//! # Dispatching body extractors by `Content-Type`
+//!
+//! [`ContentTypeExtractor`] tells which media types a body extractor accepts.
+//! It is implemented for the body extractors of every enabled feature:
+//!
+//! | Extractor                     | Accepted media types                                  |
+//! |-------------------------------|-------------------------------------------------------|
+//! | `Json`                        | `application/json`, `application/*+json`              |
+//! | `Form`                        | `application/x-www-form-urlencoded`                   |
+//! | `MsgPack`, `MsgPackRaw`       | `application/msgpack`                                 |
+//! | `Cbor`                        | `application/cbor`                                    |
+//! | `Yaml`                        | `application/yaml`                                    |
+//! | `Xml`                         | `application/xml`                                     |
+//! | `Toml`                        | `application/toml`                                    |
+//! | `Sonic`                       | `application/json`                                    |
+//! | `Form` from `axum-extra`      | `application/x-www-form-urlencoded`                   |
+//!
+//! Extractors dispatching on it, such as [`AnyBody<T>`] or `ByContentType<Either<E1, E2>>` from `extra::either`,
+//! reject requests whose media type isn't accepted by any of their alternatives with
+//! [`ContentTypeRejection::UnsupportedMediaType`].
+//!
+//! ## `AnyBody<T>`
+//!
+//! ### Feature
+//!
+//! Enable any of the `json`, `form`, `msgpack`, `cbor`, `yaml`, `xml`, `toml` or `sonic` features to use `AnyBody<T>`.
+//!
+//! ### Usage
+//!
+//! 1. Implement `Deserialize` and `Validate` for your data type `T`.
+//! 2. In your handler function, use `ValidBody<T>`, `GardeBody<T>` or `ValidatedBody<T>` as some parameter's type.
+//!    They are aliases of `Valid<AnyBody<T>>`, `Garde<AnyBody<T>>` and `Validated<AnyBody<T>>`.
+//!
+//! `AnyBody<T>` deserializes the request body with the first extractor of the table above that accepts
+//! the `Content-Type` of the request, among the enabled features. `Json` is preferred over `Sonic`.
+//! Requests with any other `Content-Type` are rejected with `415 Unsupported Media Type`.
+//!
+//! ### Example
+//!
+//! ```no_run
+//! #[cfg(all(feature = "validator", feature = "json"))]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_valid::content_type::{AnyBody, ValidBody};
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/body", post(handler))
+//!     }
+//!
+//!     async fn handler(Valid(AnyBody(parameter)): ValidBody<Parameter>) {
+//!         assert!(parameter.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[validate(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! #[cfg(all(feature = "garde", feature = "json"))]
+//! mod garde_example {
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_valid::content_type::{AnyBody, GardeBody};
+//!     use axum_valid::Garde;
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/body", post(handler))
+//!     }
+//!
+//!     async fn handler(Garde(AnyBody(parameter)): GardeBody<Parameter>) {
+//!         assert!(parameter.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[garde(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(all(feature = "validator", feature = "json"))]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(all(feature = "garde", feature = "json"))]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+#[cfg(any(
+    feature = "json",
+    feature = "form",
+    feature = "msgpack",
+    feature = "cbor",
+    feature = "yaml",
+    feature = "xml",
+    feature = "toml",
+    feature = "sonic"
+))]
+pub use any_body::*;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A body extractor that only accepts some media types.
+pub trait ContentTypeExtractor {
+    /// Returns whether the extractor accepts the media type.
+    ///
+    /// `media_type` is the lowercase `Content-Type` without parameters, such as `application/json`.
+    fn accepts(media_type: &str) -> bool;
+}
+
+/// Returns the lowercase media type of the request without parameters,
+/// or an empty string if the `Content-Type` header is missing or invalid.
+#[cfg(any(
+    feature = "extra",
+    feature = "json",
+    feature = "form",
+    feature = "msgpack",
+    feature = "cbor",
+    feature = "yaml",
+    feature = "xml",
+    feature = "toml",
+    feature = "sonic"
+))]
+pub(crate) fn media_type(headers: &axum::http::HeaderMap) -> String {
+    headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// Rejection used by extractors dispatching on `Content-Type`.
+#[derive(Debug)]
+pub enum ContentTypeRejection<E> {
+    /// None of the alternatives accepts the media type of the request.
+    ///
+    /// Responds with `415 Unsupported Media Type`.
+    UnsupportedMediaType,
+    /// The extractor accepting the media type failed.
+    Inner(E),
+}
+
+impl<E: Display> Display for ContentTypeRejection<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentTypeRejection::UnsupportedMediaType => {
+                write!(f, "Unsupported media type")
+            }
+            ContentTypeRejection::Inner(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ContentTypeRejection<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ContentTypeRejection::UnsupportedMediaType => None,
+            ContentTypeRejection::Inner(error) => Some(error),
+        }
+    }
+}
+
+impl<E: IntoResponse> IntoResponse for ContentTypeRejection<E> {
+    fn into_response(self) -> Response {
+        match self {
+            ContentTypeRejection::UnsupportedMediaType => {
+                (StatusCode::UNSUPPORTED_MEDIA_TYPE, "Unsupported media type").into_response()
+            }
+            ContentTypeRejection::Inner(error) => error.into_response(),
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "json",
+    feature = "form",
+    feature = "msgpack",
+    feature = "cbor",
+    feature = "yaml",
+    feature = "xml",
+    feature = "toml",
+    feature = "sonic"
+))]
+mod any_body {
+    use super::{media_type, ContentTypeExtractor, ContentTypeRejection};
+    use crate::HasValidate;
+    #[cfg(feature = "validator")]
+    use crate::HasValidateArgs;
+    use axum::extract::{FromRequest, Request};
+    use axum::response::{IntoResponse, Response};
+    use serde::de::DeserializeOwned;
+    use std::fmt::{Display, Formatter};
+    use std::ops::{Deref, DerefMut};
+    #[cfg(feature = "validator")]
+    use validator::ValidateArgs;
+
+    /// # `AnyBody<T>` extractor
+    ///
+    /// Deserializes the request body into `T` with the enabled extractor accepting the `Content-Type` of the request,
+    /// see the [module documentation](crate::content_type).
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct AnyBody<T>(pub T);
+
+    /// Rejection used for `AnyBody<T>`.
+    ///
+    /// The rejection of the extractor accepting the `Content-Type` is converted into its response.
+    pub type AnyBodyRejection = ContentTypeRejection<Response>;
+
+    /// `AnyBody<T>` validated with validator.
+    #[cfg(feature = "validator")]
+    pub type ValidBody<T> = crate::Valid<AnyBody<T>>;
+
+    /// `AnyBody<T>` validated with garde.
+    #[cfg(feature = "garde")]
+    pub type GardeBody<T> = crate::Garde<AnyBody<T>>;
+
+    /// `AnyBody<T>` validated with validify.
+    #[cfg(feature = "validify")]
+    pub type ValidatedBody<T> = crate::Validated<AnyBody<T>>;
+
+    impl<T> Deref for AnyBody<T> {
+        type Target = T;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl<T> DerefMut for AnyBody<T> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.0
+        }
+    }
+
+    impl<T: Display> Display for AnyBody<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl<T> AnyBody<T> {
+        /// Consumes the `AnyBody` and returns the data within.
+        pub fn into_inner(self) -> T {
+            self.0
+        }
+    }
+
+    async fn extract<E, State, T>(
+        req: Request,
+        state: &State,
+        into_inner: fn(E) -> T,
+    ) -> Result<AnyBody<T>, AnyBodyRejection>
+    where
+        E: FromRequest<State>,
+        State: Send + Sync,
+    {
+        E::from_request(req, state)
+            .await
+            .map(|extractor| AnyBody(into_inner(extractor)))
+            .map_err(|rejection| ContentTypeRejection::Inner(rejection.into_response()))
+    }
+
+    impl<State, T> FromRequest<State> for AnyBody<T>
+    where
+        State: Send + Sync,
+        T: DeserializeOwned,
+    {
+        type Rejection = AnyBodyRejection;
+
+        async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+            let media_type = media_type(req.headers());
+            #[cfg(feature = "json")]
+            if axum::Json::<T>::accepts(&media_type) {
+                return extract(req, state, |axum::Json(data)| data).await;
+            }
+            #[cfg(feature = "form")]
+            if axum::Form::<T>::accepts(&media_type) {
+                return extract(req, state, |axum::Form(data)| data).await;
+            }
+            #[cfg(feature = "msgpack")]
+            if axum_serde::MsgPack::<T>::accepts(&media_type) {
+                return extract(req, state, axum_serde::MsgPack::into_inner).await;
+            }
+            #[cfg(feature = "cbor")]
+            if axum_serde::Cbor::<T>::accepts(&media_type) {
+                return extract(req, state, axum_serde::Cbor::into_inner).await;
+            }
+            #[cfg(feature = "yaml")]
+            if axum_serde::Yaml::<T>::accepts(&media_type) {
+                return extract(req, state, axum_serde::Yaml::into_inner).await;
+            }
+            #[cfg(feature = "xml")]
+            if axum_serde::Xml::<T>::accepts(&media_type) {
+                return extract(req, state, axum_serde::Xml::into_inner).await;
+            }
+            #[cfg(feature = "toml")]
+            if axum_serde::Toml::<T>::accepts(&media_type) {
+                return extract(req, state, axum_serde::Toml::into_inner).await;
+            }
+            #[cfg(feature = "sonic")]
+            if axum_serde::Sonic::<T>::accepts(&media_type) {
+                return extract(req, state, axum_serde::Sonic::into_inner).await;
+            }
+            Err(ContentTypeRejection::UnsupportedMediaType)
+        }
+    }
+
+    impl<T> HasValidate for AnyBody<T> {
+        type Validate = T;
+        fn get_validate(&self) -> &T {
+            &self.0
+        }
+    }
+
+    #[cfg(feature = "validator")]
+    impl<'v, T: ValidateArgs<'v>> HasValidateArgs<'v> for AnyBody<T> {
+        type ValidateArgs = T;
+        fn get_validate_args(&self) -> &Self::ValidateArgs {
+            &self.0
+        }
+    }
+
+    #[cfg(feature = "validify")]
+    impl<T: validify::Modify> crate::HasModify for AnyBody<T> {
+        type Modify = T;
+
+        fn get_modify(&mut self) -> &mut Self::Modify {
+            &mut self.0
+        }
+    }
+
+    #[cfg(feature = "validify")]
+    impl<T> crate::PayloadExtractor for AnyBody<T> {
+        type Payload = T;
+
+        fn get_payload(self) -> Self::Payload {
+            self.0
+        }
+    }
+
+    #[cfg(feature = "validify")]
+    impl<T: validify::Validify + validify::ValidifyPayload> crate::HasValidify for AnyBody<T> {
+        type Validify = T;
+        type PayloadExtractor = AnyBody<T::Payload>;
+
+        fn from_validify(v: Self::Validify) -> Self {
+            AnyBody(v)
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T> ContentTypeExtractor for axum::Json<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == "application/json"
+            || media_type
+                .strip_prefix("application/")
+                .is_some_and(|subtype| subtype.ends_with("+json"))
+    }
+}
+
+#[cfg(feature = "form")]
+impl<T> ContentTypeExtractor for axum::Form<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == "application/x-www-form-urlencoded"
+    }
+}
+
+#[cfg(feature = "extra_form")]
+impl<T> ContentTypeExtractor for axum_extra::extract::Form<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == "application/x-www-form-urlencoded"
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T> ContentTypeExtractor for axum_serde::MsgPack<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == Self::CONTENT_TYPE
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T> ContentTypeExtractor for axum_serde::MsgPackRaw<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == Self::CONTENT_TYPE
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<T> ContentTypeExtractor for axum_serde::Cbor<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == Self::CONTENT_TYPE
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<T> ContentTypeExtractor for axum_serde::Yaml<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == Self::CONTENT_TYPE
+    }
+}
+
+#[cfg(feature = "xml")]
+impl<T> ContentTypeExtractor for axum_serde::Xml<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == Self::CONTENT_TYPE
+    }
+}
+
+#[cfg(feature = "toml")]
+impl<T> ContentTypeExtractor for axum_serde::Toml<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == Self::CONTENT_TYPE
+    }
+}
+
+#[cfg(feature = "sonic")]
+impl<T> ContentTypeExtractor for axum_serde::Sonic<T> {
+    fn accepts(media_type: &str) -> bool {
+        media_type == Self::CONTENT_TYPE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(
+        feature = "extra",
+        feature = "json",
+        feature = "form",
+        feature = "msgpack",
+        feature = "cbor",
+        feature = "yaml",
+        feature = "xml",
+        feature = "toml",
+        feature = "sonic"
+    ))]
+    #[test]
+    fn media_type_of_headers() {
+        use axum::http::{HeaderMap, HeaderValue};
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(media_type(&headers), "");
+        headers.insert(
+            axum::http::header::CONTENT_TYPE,
+            HeaderValue::from_static("Application/JSON; charset=utf-8"),
+        );
+        assert_eq!(media_type(&headers), "application/json");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_accepts() {
+        assert!(axum::Json::<()>::accepts("application/json"));
+        assert!(axum::Json::<()>::accepts("application/cloudevents+json"));
+        assert!(!axum::Json::<()>::accepts("text/json"));
+        assert!(!axum::Json::<()>::accepts(""));
+    }
+
+    #[cfg(all(feature = "validator", feature = "json", feature = "form"))]
+    #[tokio::test]
+    async fn valid_body() -> anyhow::Result<()> {
+        use crate::{Valid, VALIDATION_ERROR_STATUS};
+        use axum::routing::post;
+        use axum::Router;
+        use reqwest::Client;
+        use serde::{Deserialize, Serialize};
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+        use validator::Validate;
+
+        #[derive(Debug, Deserialize, Serialize, Validate)]
+        struct Parameter {
+            #[validate(range(min = 5, max = 10))]
+            v0: i32,
+        }
+
+        async fn handler(Valid(AnyBody(parameter)): ValidBody<Parameter>) -> String {
+            parameter.v0.to_string()
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        let client = Client::new();
+
+        let response = client.post(&url).json(&Parameter { v0: 5 }).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "5");
+
+        let response = client.post(&url).form(&Parameter { v0: 6 }).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "6");
+
+        let response = client.post(&url).json(&Parameter { v0: 11 }).send().await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+
+        #[cfg(feature = "msgpack")]
+        {
+            let response = client
+                .post(&url)
+                .header("content-type", "application/msgpack")
+                .body(rmp_serde::to_vec_named(&Parameter { v0: 7 })?)
+                .send()
+                .await?;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.text().await?, "7");
+        }
+
+        let response = client
+            .post(&url)
+            .header("content-type", "text/plain")
+            .body("v0=5")
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let response = client.post(&url).body("v0=5").send().await?;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        Ok(())
+    }
+}
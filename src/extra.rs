@@ -8,7 +8,10 @@
 //!
 //! * [`self`] : `Cache<T>`
 //! * [`self`] : `WithRejection<T, R>`
+//! * [`cookie`] : `Cookies<T, J>`
+//! * [`either`] : `Either<E1, E2>` and `ByContentType<Either<E1, E2>>`
 //! * [`form`] : `Form<T>`
+//! * [`json_lines`] : `JsonLines<T>`
 //! * [`protobuf`] : `Protobuf<T>`
 //! * [`query`] : `Query<T>`
 //! * [`typed_path`] : `T: TypedPath`
@@ -434,8 +437,16 @@
 //! }
 //! ```
 
+#[cfg(feature = "extra_cookie")]
+pub mod cookie;
+pub mod either;
 #[cfg(feature = "extra_form")]
 pub mod form;
+#[cfg(all(
+    feature = "extra_json_lines",
+    any(feature = "validator", feature = "garde", feature = "validify")
+))]
+pub mod json_lines;
 #[cfg(feature = "extra_protobuf")]
 pub mod protobuf;
 #[cfg(feature = "extra_query")]
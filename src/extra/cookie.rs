@@ -0,0 +1,433 @@
+//! # Support for cookies from `axum-extra`
+//!
+//! ## Feature
+//!
+//! Enable the `extra_cookie` feature to use `Valid<Cookies<T>>`.
+//! Enable `extra_cookie_signed` or `extra_cookie_private` to read the cookies from a `SignedCookieJar` or a `PrivateCookieJar`.
+//!
+//! ## Usage
+//!
+//! 1. Implement `Deserialize` and `Validate` for your data type `T`. Each field is read from the cookie with the same name.
+//! 2. In your handler function, use `Valid<Cookies<T>>` as some parameter's type.
+//!    Use `Valid<Cookies<T, SignedCookieJar>>` or `Valid<Cookies<T, PrivateCookieJar>>` to only accept cookies
+//!    that were signed or encrypted with your key, which must be provided through state like the jars themselves.
+//!
+//! Cookie values are deserialized like query string values, so numbers, booleans and optional fields are supported.
+//! Values are read as the jar decodes them, without any further unescaping, so `+` is kept as is.
+//! Cookies that can't be deserialized are rejected with `400 Bad Request`,
+//! invalid data is rejected with the standard validation rejection.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::routing::get;
+//!     use axum::Router;
+//!     use axum_valid::extra::cookie::Cookies;
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/cookies", get(handler))
+//!     }
+//!
+//!     async fn handler(Valid(Cookies(preferences, _)): Valid<Cookies<Preferences>>) {
+//!         assert!(preferences.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Preferences {
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub theme: String,
+//!         #[validate(range(min = 10, max = 100))]
+//!         pub page_size: Option<u32>,
+//!     }
+//! }
+//!
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::routing::get;
+//!     use axum::Router;
+//!     use axum_valid::extra::cookie::Cookies;
+//!     use axum_valid::Garde;
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/cookies", get(handler))
+//!     }
+//!
+//!     async fn handler(Garde(Cookies(preferences, _)): Garde<Cookies<Preferences>>) {
+//!         assert!(preferences.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Preferences {
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub theme: String,
+//!         #[garde(range(min = 10, max = 100))]
+//!         pub page_size: Option<u32>,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::HasValidate;
+#[cfg(feature = "validator")]
+use crate::HasValidateArgs;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum_extra::extract::CookieJar;
+use serde::de::DeserializeOwned;
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+#[cfg(feature = "validator")]
+use validator::ValidateArgs;
+
+/// # `Cookies<T, J>` extractor
+///
+/// Deserializes the cookies of the jar `J` into `T`, see the [module documentation](self).
+///
+/// The second field only records the jar type the cookies are read from.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cookies<T, J = CookieJar>(pub T, pub PhantomData<J>);
+
+impl<T, J> Deref for Cookies<T, J> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, J> DerefMut for Cookies<T, J> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display, J> Display for Cookies<T, J> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T, J> Cookies<T, J> {
+    /// Consumes the `Cookies` and returns the data within.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// A cookie jar `Cookies<T, J>` can read cookies from.
+pub trait CookieSource {
+    /// Returns the names and values of the cookies in the jar.
+    fn cookies(&self) -> Vec<(String, String)>;
+}
+
+impl CookieSource for CookieJar {
+    fn cookies(&self) -> Vec<(String, String)> {
+        self.iter()
+            .map(|cookie| (cookie.name().to_owned(), cookie.value().to_owned()))
+            .collect()
+    }
+}
+
+/// Only the cookies with a valid signature are read.
+#[cfg(feature = "extra_cookie_signed")]
+impl<K> CookieSource for axum_extra::extract::SignedCookieJar<K> {
+    fn cookies(&self) -> Vec<(String, String)> {
+        self.iter()
+            .map(|cookie| (cookie.name().to_owned(), cookie.value().to_owned()))
+            .collect()
+    }
+}
+
+/// Only the cookies that can be decrypted are read.
+#[cfg(feature = "extra_cookie_private")]
+impl<K> CookieSource for axum_extra::extract::PrivateCookieJar<K> {
+    fn cookies(&self) -> Vec<(String, String)> {
+        self.iter()
+            .map(|cookie| (cookie.name().to_owned(), cookie.value().to_owned()))
+            .collect()
+    }
+}
+
+crate::pairs::pairs_rejection!(
+    /// Rejection used for `Cookies<T, J>` when the cookies can't be deserialized.
+    CookiesRejection,
+    "cookies"
+);
+
+impl<State, T, J> FromRequestParts<State> for Cookies<T, J>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+    J: FromRequestParts<State, Rejection = Infallible> + CookieSource,
+{
+    type Rejection = CookiesRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let jar = match J::from_request_parts(parts, state).await {
+            Ok(jar) => jar,
+            Err(never) => match never {},
+        };
+        crate::pairs::from_pairs(jar.cookies())
+            .map(|data| Cookies(data, PhantomData))
+            .map_err(CookiesRejection)
+    }
+}
+
+impl<T, J> HasValidate for Cookies<T, J> {
+    type Validate = T;
+    fn get_validate(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<'v, T: ValidateArgs<'v>, J> HasValidateArgs<'v> for Cookies<T, J> {
+    type ValidateArgs = T;
+    fn get_validate_args(&self) -> &Self::ValidateArgs {
+        &self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Modify, J> crate::HasModify for Cookies<T, J> {
+    type Modify = T;
+
+    fn get_modify(&mut self) -> &mut Self::Modify {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T, J> crate::PayloadExtractor for Cookies<T, J> {
+    type Payload = T;
+
+    fn get_payload(self) -> Self::Payload {
+        self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Validify + validify::ValidifyPayload, J> crate::HasValidify for Cookies<T, J> {
+    type Validify = T;
+    type PayloadExtractor = Cookies<T::Payload, J>;
+
+    fn from_validify(v: Self::Validify) -> Self {
+        Cookies(v, PhantomData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::header::COOKIE;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::Router;
+    use reqwest::Client;
+    use std::net::SocketAddr;
+    use tokio::net::TcpListener;
+
+    async fn serve(router: Router) -> anyhow::Result<String> {
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        Ok(format!("http://{addr}"))
+    }
+
+    async fn status(url: &str, cookie: &str) -> anyhow::Result<StatusCode> {
+        let response = Client::new().get(url).header(COOKIE, cookie).send().await?;
+        Ok(response.status())
+    }
+
+    #[cfg(feature = "validator")]
+    mod validator_data {
+        use serde::Deserialize;
+        use validator::Validate;
+
+        #[derive(Debug, Deserialize, Validate)]
+        pub struct Preferences {
+            #[validate(length(min = 1, max = 10))]
+            pub theme: String,
+            #[validate(range(min = 10, max = 100))]
+            pub page_size: Option<u32>,
+        }
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn valid_cookies() -> anyhow::Result<()> {
+        use crate::{Valid, VALIDATION_ERROR_STATUS};
+        use validator_data::Preferences;
+
+        async fn handler(Valid(Cookies(preferences, _)): Valid<Cookies<Preferences>>) -> String {
+            format!("{} {:?}", preferences.theme, preferences.page_size)
+        }
+
+        let url = serve(Router::new().route("/", get(handler))).await?;
+
+        let response = Client::new()
+            .get(&url)
+            .header(COOKIE, "theme=dark; page_size=20; session=abc")
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "dark Some(20)");
+
+        let response = Client::new()
+            .get(&url)
+            .header(COOKIE, "theme=a+b%20c")
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "a+b c None");
+
+        assert_eq!(status(&url, "theme=dark").await?, StatusCode::OK);
+        assert_eq!(
+            status(&url, "theme=dark; page_size=5").await?,
+            VALIDATION_ERROR_STATUS
+        );
+        assert_eq!(
+            status(&url, "theme=dark; page_size=large").await?,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(status(&url, "page_size=20").await?, StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[cfg(feature = "garde")]
+    #[tokio::test]
+    async fn garde_cookies() -> anyhow::Result<()> {
+        use crate::{Garde, VALIDATION_ERROR_STATUS};
+        use garde::Validate;
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize, Validate)]
+        struct Preferences {
+            #[garde(length(min = 1, max = 10))]
+            theme: String,
+        }
+
+        async fn handler(Garde(Cookies(preferences, _)): Garde<Cookies<Preferences>>) -> String {
+            preferences.theme
+        }
+
+        let url = serve(Router::new().route("/", get(handler))).await?;
+        assert_eq!(status(&url, "theme=dark").await?, StatusCode::OK);
+        assert_eq!(
+            status(&url, "theme=transparent").await?,
+            VALIDATION_ERROR_STATUS
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "validify")]
+    #[tokio::test]
+    async fn validified_cookies() -> anyhow::Result<()> {
+        use crate::{Validified, VALIDATION_ERROR_STATUS};
+        use validify::{Payload, Validify};
+
+        #[derive(Debug, Clone, serde::Deserialize, Validify, Payload)]
+        struct Preferences {
+            #[modify(trim, lowercase)]
+            #[validate(length(min = 1, max = 10))]
+            theme: String,
+        }
+
+        async fn handler(
+            Validified(Cookies(preferences, _)): Validified<Cookies<Preferences>>,
+        ) -> String {
+            preferences.theme
+        }
+
+        let url = serve(Router::new().route("/", get(handler))).await?;
+        let response = Client::new()
+            .get(&url)
+            .header(COOKIE, "theme=DARK")
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "dark");
+        assert_eq!(status(&url, "session=abc").await?, VALIDATION_ERROR_STATUS);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "validator", feature = "extra_cookie_signed"))]
+    #[tokio::test]
+    async fn valid_signed_cookies() -> anyhow::Result<()> {
+        use crate::{Valid, VALIDATION_ERROR_STATUS};
+        use axum::extract::FromRef;
+        use axum::http::header::SET_COOKIE;
+        use axum_extra::extract::cookie::{Cookie, Key};
+        use axum_extra::extract::SignedCookieJar;
+        use validator_data::Preferences;
+
+        #[derive(Clone)]
+        struct AppState {
+            key: Key,
+        }
+
+        impl FromRef<AppState> for Key {
+            fn from_ref(state: &AppState) -> Self {
+                state.key.clone()
+            }
+        }
+
+        async fn handler(
+            Valid(Cookies(preferences, _)): Valid<Cookies<Preferences, SignedCookieJar>>,
+        ) -> String {
+            preferences.theme
+        }
+
+        let key = Key::generate();
+        let sign = |name: &'static str, value: &'static str| -> anyhow::Result<String> {
+            let response = SignedCookieJar::new(key.clone())
+                .add(Cookie::new(name, value))
+                .into_response();
+            let set_cookie = response
+                .headers()
+                .get(SET_COOKIE)
+                .ok_or_else(|| anyhow::anyhow!("missing set-cookie"))?
+                .to_str()?;
+            Ok(set_cookie.split(';').next().unwrap_or_default().to_owned())
+        };
+
+        let router = Router::new()
+            .route("/", get(handler))
+            .with_state(AppState { key: key.clone() });
+        let url = serve(router).await?;
+
+        assert_eq!(status(&url, &sign("theme", "dark")?).await?, StatusCode::OK);
+        assert_eq!(
+            status(&url, &sign("theme", "transparent")?).await?,
+            VALIDATION_ERROR_STATUS
+        );
+        // Unsigned cookies are ignored, so the required field is missing.
+        assert_eq!(status(&url, "theme=dark").await?, StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+}
@@ -0,0 +1,419 @@
+//! # Support for `Either<E1, E2>` from `axum-extra`
+//!
+//! ## Feature
+//!
+//! Enable the `extra` feature to use `Valid<Either<E1, E2>>` and `Valid<ByContentType<Either<E1, E2>>>`.
+//! `Either3` to `Either8` are supported as well.
+//!
+//! ## Usage
+//!
+//! ### `Valid<Either<E1, E2>>`
+//!
+//! All the alternatives must hold the same data type `T`, such as `Either<Query<T>, Headers<T>>`.
+//! Whichever alternative is extracted, `T` is validated.
+//!
+//! `Either` only tries request parts extractors. To accept one of several body formats,
+//! wrap it with `ByContentType`.
+//!
+//! ### `Valid<ByContentType<Either<E1, E2>>>`
+//!
+//! `ByContentType` extracts the request body with the first alternative that accepts the `Content-Type`
+//! of the request (see [`ContentTypeExtractor`](crate::content_type::ContentTypeExtractor)),
+//! such as `ByContentType<Either<Json<T>, Form<T>>>`.
+//! Requests with a `Content-Type` accepted by none of the alternatives are rejected with `415 Unsupported Media Type`.
+//!
+//! `Validified` is not supported, as it rebuilds the extractor from the data alone and would lose
+//! the extracted alternative. Use `ValidifiedByRef`, `Modified` or `Validated` instead, which keep it.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::{Form, Json, Router};
+//!     use axum_extra::either::Either;
+//!     use axum_valid::extra::either::ByContentType;
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/either", post(handler))
+//!     }
+//!
+//!     async fn handler(
+//!         Valid(ByContentType(parameter)): Valid<ByContentType<Either<Json<Parameter>, Form<Parameter>>>>,
+//!     ) {
+//!         let parameter = match parameter {
+//!             Either::E1(Json(parameter)) => parameter,
+//!             Either::E2(Form(parameter)) => parameter,
+//!         };
+//!         assert!(parameter.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[validate(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::routing::post;
+//!     use axum::{Form, Json, Router};
+//!     use axum_extra::either::Either;
+//!     use axum_valid::extra::either::ByContentType;
+//!     use axum_valid::{Garde, HasValidate};
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/either", post(handler))
+//!     }
+//!
+//!     async fn handler(
+//!         Garde(parameter): Garde<ByContentType<Either<Json<Parameter>, Form<Parameter>>>>,
+//!     ) {
+//!         assert!(parameter.get_validate().validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[garde(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::content_type::{media_type, ContentTypeExtractor, ContentTypeRejection};
+use crate::HasValidate;
+#[cfg(feature = "validator")]
+use crate::HasValidateArgs;
+use axum::extract::{FromRequest, Request};
+use axum_extra::either::{Either, Either3, Either4, Either5, Either6, Either7, Either8};
+use std::ops::{Deref, DerefMut};
+
+/// # `ByContentType<E>` extractor
+///
+/// Extracts the request body with the alternative of `E` accepting the `Content-Type` of the request,
+/// see the [module documentation](self).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByContentType<E>(pub E);
+
+impl<E> Deref for ByContentType<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for ByContentType<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<E> ByContentType<E> {
+    /// Consumes the `ByContentType` and returns the extractor within.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+impl<E: HasValidate> HasValidate for ByContentType<E> {
+    type Validate = E::Validate;
+
+    fn get_validate(&self) -> &Self::Validate {
+        self.0.get_validate()
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<'v, E: HasValidateArgs<'v>> HasValidateArgs<'v> for ByContentType<E> {
+    type ValidateArgs = E::ValidateArgs;
+
+    fn get_validate_args(&self) -> &Self::ValidateArgs {
+        self.0.get_validate_args()
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<E: crate::HasModify> crate::HasModify for ByContentType<E> {
+    type Modify = E::Modify;
+
+    fn get_modify(&mut self) -> &mut Self::Modify {
+        self.0.get_modify()
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<E: crate::PayloadExtractor> crate::PayloadExtractor for ByContentType<E> {
+    type Payload = E::Payload;
+
+    fn get_payload(self) -> Self::Payload {
+        self.0.get_payload()
+    }
+}
+
+macro_rules! impl_for_either {
+    ($either:ident => $first:ident, $($rest:ident),+) => {
+        impl<$first: HasValidate, $($rest: HasValidate<Validate = $first::Validate>),+> HasValidate
+            for $either<$first, $($rest),+>
+        {
+            type Validate = $first::Validate;
+
+            fn get_validate(&self) -> &Self::Validate {
+                match self {
+                    $either::$first(extractor) => extractor.get_validate(),
+                    $($either::$rest(extractor) => extractor.get_validate()),+
+                }
+            }
+        }
+
+        #[cfg(feature = "validator")]
+        impl<'v, $first: HasValidateArgs<'v>, $($rest: HasValidateArgs<'v, ValidateArgs = $first::ValidateArgs>),+>
+            HasValidateArgs<'v> for $either<$first, $($rest),+>
+        {
+            type ValidateArgs = $first::ValidateArgs;
+
+            fn get_validate_args(&self) -> &Self::ValidateArgs {
+                match self {
+                    $either::$first(extractor) => extractor.get_validate_args(),
+                    $($either::$rest(extractor) => extractor.get_validate_args()),+
+                }
+            }
+        }
+
+        #[cfg(feature = "validify")]
+        impl<$first: crate::HasModify, $($rest: crate::HasModify<Modify = $first::Modify>),+>
+            crate::HasModify for $either<$first, $($rest),+>
+        {
+            type Modify = $first::Modify;
+
+            fn get_modify(&mut self) -> &mut Self::Modify {
+                match self {
+                    $either::$first(extractor) => extractor.get_modify(),
+                    $($either::$rest(extractor) => extractor.get_modify()),+
+                }
+            }
+        }
+
+        #[cfg(feature = "validify")]
+        impl<$first: crate::PayloadExtractor, $($rest: crate::PayloadExtractor<Payload = $first::Payload>),+>
+            crate::PayloadExtractor for $either<$first, $($rest),+>
+        {
+            type Payload = $first::Payload;
+
+            fn get_payload(self) -> Self::Payload {
+                match self {
+                    $either::$first(extractor) => extractor.get_payload(),
+                    $($either::$rest(extractor) => extractor.get_payload()),+
+                }
+            }
+        }
+
+        impl<State, $first, $($rest),+> FromRequest<State> for ByContentType<$either<$first, $($rest),+>>
+        where
+            State: Send + Sync,
+            $first: FromRequest<State> + ContentTypeExtractor,
+            $($rest: FromRequest<State> + ContentTypeExtractor),+
+        {
+            type Rejection = ContentTypeRejection<$either<$first::Rejection, $($rest::Rejection),+>>;
+
+            async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+                let media_type = media_type(req.headers());
+                if $first::accepts(&media_type) {
+                    return $first::from_request(req, state)
+                        .await
+                        .map(|extractor| ByContentType($either::$first(extractor)))
+                        .map_err(|rejection| ContentTypeRejection::Inner($either::$first(rejection)));
+                }
+                $(
+                    if $rest::accepts(&media_type) {
+                        return $rest::from_request(req, state)
+                            .await
+                            .map(|extractor| ByContentType($either::$rest(extractor)))
+                            .map_err(|rejection| ContentTypeRejection::Inner($either::$rest(rejection)));
+                    }
+                )+
+                Err(ContentTypeRejection::UnsupportedMediaType)
+            }
+        }
+    };
+}
+
+impl_for_either!(Either => E1, E2);
+impl_for_either!(Either3 => E1, E2, E3);
+impl_for_either!(Either4 => E1, E2, E3, E4);
+impl_for_either!(Either5 => E1, E2, E3, E4, E5);
+impl_for_either!(Either6 => E1, E2, E3, E4, E5, E6);
+impl_for_either!(Either7 => E1, E2, E3, E4, E5, E6, E7);
+impl_for_either!(Either8 => E1, E2, E3, E4, E5, E6, E7, E8);
+
+#[cfg(all(test, feature = "validator", feature = "json", feature = "form"))]
+mod tests {
+    use super::*;
+    use crate::{Valid, VALIDATION_ERROR_STATUS};
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::{Form, Json, Router};
+    use reqwest::Client;
+    use serde::{Deserialize, Serialize};
+    use std::net::SocketAddr;
+    use tokio::net::TcpListener;
+    use validator::Validate;
+
+    #[derive(Debug, Deserialize, Serialize, Validate)]
+    struct Parameter {
+        #[validate(range(min = 5, max = 10))]
+        v0: i32,
+    }
+
+    async fn by_content_type(
+        Valid(ByContentType(parameter)): Valid<
+            ByContentType<Either<Json<Parameter>, Form<Parameter>>>,
+        >,
+    ) -> &'static str {
+        match parameter {
+            Either::E1(_) => "json",
+            Either::E2(_) => "form",
+        }
+    }
+
+    #[cfg(all(feature = "query", feature = "headers"))]
+    async fn either(
+        Valid(parameter): Valid<
+            Either<axum::extract::Query<Parameter>, crate::headers::Headers<Parameter>>,
+        >,
+    ) -> String {
+        parameter.get_validate().v0.to_string()
+    }
+
+    #[tokio::test]
+    async fn valid_by_content_type() -> anyhow::Result<()> {
+        let router = Router::new().route("/", post(by_content_type));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        let client = Client::new();
+
+        let response = client.post(&url).json(&Parameter { v0: 5 }).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "json");
+
+        let response = client.post(&url).form(&Parameter { v0: 5 }).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "form");
+
+        let response = client.post(&url).form(&Parameter { v0: 11 }).send().await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+
+        let response = client
+            .post(&url)
+            .header("content-type", "application/json")
+            .body("{")
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = client
+            .post(&url)
+            .header("content-type", "text/plain")
+            .body("v0=5")
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "query", feature = "headers"))]
+    #[tokio::test]
+    async fn valid_either() -> anyhow::Result<()> {
+        let router = Router::new().route("/", post(either));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        let client = Client::new();
+
+        let response = client.post(&url).query(&[("v0", "6")]).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "6");
+
+        let response = client.post(&url).header("v0", "7").send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "7");
+
+        let response = client.post(&url).query(&[("v0", "1")]).send().await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        Ok(())
+    }
+
+    #[cfg(feature = "validify")]
+    #[tokio::test]
+    async fn validified_by_ref_by_content_type() -> anyhow::Result<()> {
+        use crate::ValidifiedByRef;
+
+        #[derive(Debug, Deserialize, validify::Validify)]
+        struct Data {
+            #[modify(trim)]
+            #[validate(length(min = 1, max = 10))]
+            v1: String,
+        }
+
+        async fn handler(
+            ValidifiedByRef(ByContentType(data)): ValidifiedByRef<
+                ByContentType<Either<Json<Data>, Form<Data>>>,
+            >,
+        ) -> String {
+            match data {
+                Either::E1(Json(data)) => format!("json {}", data.v1),
+                Either::E2(Form(data)) => format!("form {}", data.v1),
+            }
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        let client = Client::new();
+
+        let response = client.post(&url).form(&[("v1", " a ")]).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "form a");
+
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "v1": " b " }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "json b");
+
+        let response = client.post(&url).form(&[("v1", "  ")]).send().await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        Ok(())
+    }
+}
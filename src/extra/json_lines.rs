@@ -0,0 +1,467 @@
+//! # Support for `JsonLines<T>` from `axum-extra`
+//!
+//! ## Feature
+//!
+//! Enable the `extra_json_lines` feature to use `ValidJsonLines<T>`, `GardeJsonLines<T>` or `ValidifyJsonLines<T>`.
+//!
+//! ## Usage
+//!
+//! 1. Implement `Deserialize` and `Validate` for your data type `T`.
+//! 2. In your handler function, use `ValidJsonLines<T>` (or the alias for your validation library) as some parameter's type.
+//! 3. Consume the stream: each line yields either the validated data or an `ItemError`, carrying the line number and the errors.
+//! 4. Optionally, call `with_policy` to choose how the stream reacts to invalid lines, see `ItemPolicy`.
+//!
+//! Unlike the other extractors, the request is not rejected when an item is invalid,
+//! as the items are only parsed and validated while the stream is consumed.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_valid::extra::json_lines::{ItemPolicy, ValidJsonLines};
+//!     use futures_util::StreamExt;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/import", post(handler))
+//!     }
+//!
+//!     async fn handler(lines: ValidJsonLines<Parameter>) -> String {
+//!         let mut lines = lines.with_policy(ItemPolicy::SkipAndReport);
+//!         let mut imported = 0;
+//!         let mut report = Vec::new();
+//!         while let Some(item) = lines.next().await {
+//!             match item {
+//!                 Ok(parameter) => {
+//!                     assert!(parameter.validate().is_ok());
+//!                     imported += 1;
+//!                 }
+//!                 Err(error) => report.push(error.to_string()),
+//!             }
+//!         }
+//!         format!("{imported} imported, errors: {report:?}")
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[validate(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_valid::extra::json_lines::GardeJsonLines;
+//!     use futures_util::StreamExt;
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/import", post(handler))
+//!     }
+//!
+//!     // Stops at the first invalid line by default
+//!     async fn handler(mut lines: GardeJsonLines<Parameter>) -> Result<(), String> {
+//!         while let Some(item) = lines.next().await {
+//!             let parameter = item.map_err(|error| error.to_string())?;
+//!             assert!(parameter.validate().is_ok());
+//!         }
+//!         Ok(())
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[garde(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use axum::extract::{FromRequest, Request};
+use axum_extra::json_lines::JsonLines;
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Policy applied by `ItemStream` to invalid lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ItemPolicy {
+    /// Yield the first error, then end the stream.
+    #[default]
+    FailFast,
+    /// Yield an error for every invalid line, and keep yielding the valid ones.
+    SkipAndReport,
+    /// Like `SkipAndReport`, but tolerate at most the given number of errors:
+    /// the error exceeding it is yielded, then the stream ends.
+    ///
+    /// `MaxErrors(0)` behaves like `FailFast`, yielding the valid lines up to the first invalid one.
+    MaxErrors(usize),
+}
+
+impl ItemPolicy {
+    fn allows(&self, errors: usize) -> bool {
+        match self {
+            ItemPolicy::FailFast => errors == 0,
+            ItemPolicy::SkipAndReport => true,
+            ItemPolicy::MaxErrors(max) => errors <= *max,
+        }
+    }
+}
+
+/// Error of a single line of a JSON Lines body.
+#[derive(Debug)]
+pub struct ItemError<V> {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// What went wrong with this line
+    pub kind: ItemErrorKind<V>,
+}
+
+/// Kind of `ItemError`.
+#[derive(Debug)]
+pub enum ItemErrorKind<V> {
+    /// The line could not be read or deserialized.
+    Inner(axum::Error),
+    /// The line was deserialized, but failed validation.
+    Valid(V),
+}
+
+impl<V: Display> Display for ItemError<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ItemErrorKind::Inner(error) => write!(f, "line {}: {error}", self.line),
+            ItemErrorKind::Valid(errors) => write!(f, "line {}: {errors}", self.line),
+        }
+    }
+}
+
+impl<V: Error + 'static> Error for ItemError<V> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ItemErrorKind::Inner(e) => Some(e),
+            ItemErrorKind::Valid(ve) => Some(ve),
+        }
+    }
+}
+
+type Check<T, V> = Box<dyn Fn(&T) -> Result<(), V> + Send + Sync>;
+
+/// # `ItemStream` data extractor
+///
+/// `ItemStream` wraps `JsonLines<T>` from `axum-extra`, validating each line as the stream is consumed,
+/// see the [module documentation](self).
+///
+/// Use it through `ValidJsonLines<T>`, `GardeJsonLines<T>` or `ValidifyJsonLines<T>`.
+///
+pub struct ItemStream<T, V> {
+    lines: Pin<Box<JsonLines<T>>>,
+    check: Check<T, V>,
+    policy: ItemPolicy,
+    line: usize,
+    errors: usize,
+}
+
+/// `ItemStream` validating each line with validator.
+#[cfg(feature = "validator")]
+pub type ValidJsonLines<T> = ItemStream<T, validator::ValidationErrors>;
+
+/// `ItemStream` validating each line with garde.
+#[cfg(feature = "garde")]
+pub type GardeJsonLines<T> = ItemStream<T, garde::Report>;
+
+/// `ItemStream` validating each line with validify.
+#[cfg(feature = "validify")]
+pub type ValidifyJsonLines<T> = ItemStream<T, validify::ValidationErrors>;
+
+impl<T, V> ItemStream<T, V> {
+    fn new(lines: JsonLines<T>, check: Check<T, V>) -> Self {
+        ItemStream {
+            lines: Box::pin(lines),
+            check,
+            policy: ItemPolicy::default(),
+            line: 0,
+            errors: 0,
+        }
+    }
+
+    /// Sets the policy applied to invalid lines, `ItemPolicy::FailFast` by default.
+    pub fn with_policy(mut self, policy: ItemPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the policy applied to invalid lines.
+    pub fn policy(&self) -> ItemPolicy {
+        self.policy
+    }
+}
+
+impl<T, V> Debug for ItemStream<T, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemStream")
+            .field("policy", &self.policy)
+            .field("line", &self.line)
+            .field("errors", &self.errors)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, V> Stream for ItemStream<T, V> {
+    type Item = Result<T, ItemError<V>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if !this.policy.allows(this.errors) {
+            return Poll::Ready(None);
+        }
+        let item = match this.lines.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => item,
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        };
+        this.line += 1;
+        let kind = match item {
+            Ok(data) => match (this.check)(&data) {
+                Ok(()) => return Poll::Ready(Some(Ok(data))),
+                Err(errors) => ItemErrorKind::Valid(errors),
+            },
+            Err(error) => ItemErrorKind::Inner(error),
+        };
+        this.errors += 1;
+        Poll::Ready(Some(Err(ItemError {
+            line: this.line,
+            kind,
+        })))
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<State, T> FromRequest<State> for ItemStream<T, validator::ValidationErrors>
+where
+    State: Send + Sync,
+    T: DeserializeOwned + validator::Validate,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let lines = JsonLines::<T>::from_request(req, state).await?;
+        Ok(ItemStream::new(lines, Box::new(|data: &T| data.validate())))
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<State, T, Context> FromRequest<State> for ItemStream<T, garde::Report>
+where
+    State: Send + Sync,
+    T: DeserializeOwned + garde::Validate<Context = Context>,
+    Context: Send + Sync + axum::extract::FromRef<State> + 'static,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let context: Context = axum::extract::FromRef::from_ref(state);
+        let lines = JsonLines::<T>::from_request(req, state).await?;
+        Ok(ItemStream::new(
+            lines,
+            Box::new(move |data: &T| data.validate_with(&context)),
+        ))
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<State, T> FromRequest<State> for ItemStream<T, validify::ValidationErrors>
+where
+    State: Send + Sync,
+    T: DeserializeOwned + validify::Validate,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let lines = JsonLines::<T>::from_request(req, state).await?;
+        Ok(ItemStream::new(lines, Box::new(|data: &T| data.validate())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use futures_util::StreamExt;
+
+    fn request(lines: &[&str]) -> Request {
+        Request::builder()
+            .body(Body::from(lines.join("\n")))
+            .expect("build request")
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn valid_json_lines() {
+        use validator::Validate;
+
+        #[derive(Debug, serde::Deserialize, Validate)]
+        struct Data {
+            #[validate(range(min = 5, max = 10))]
+            v0: i32,
+        }
+
+        let body = [
+            r#"{"v0":5}"#,
+            r#"{"v0":11}"#,
+            r#"{"v0":"x"}"#,
+            r#"{"v0":10}"#,
+        ];
+
+        async fn collect(
+            lines: ValidJsonLines<Data>,
+        ) -> Vec<Result<Data, ItemError<validator::ValidationErrors>>> {
+            lines.collect().await
+        }
+
+        let lines = ValidJsonLines::<Data>::from_request(request(&body), &())
+            .await
+            .expect("infallible");
+        assert_eq!(lines.policy(), ItemPolicy::FailFast);
+        let items = collect(lines).await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().expect("line 1").v0, 5);
+        let error = items[1].as_ref().expect_err("line 2");
+        assert_eq!(error.line, 2);
+        assert!(
+            matches!(&error.kind, ItemErrorKind::Valid(errors) if errors.field_errors().contains_key("v0"))
+        );
+
+        let lines = ValidJsonLines::<Data>::from_request(request(&body), &())
+            .await
+            .expect("infallible")
+            .with_policy(ItemPolicy::SkipAndReport);
+        let items = collect(lines).await;
+        assert_eq!(items.len(), 4);
+        assert!(matches!(
+            items[2],
+            Err(ItemError {
+                line: 3,
+                kind: ItemErrorKind::Inner(_)
+            })
+        ));
+        assert_eq!(items[3].as_ref().expect("line 4").v0, 10);
+
+        let lines = ValidJsonLines::<Data>::from_request(request(&body), &())
+            .await
+            .expect("infallible")
+            .with_policy(ItemPolicy::MaxErrors(2));
+        let items = collect(lines).await;
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[3].as_ref().expect("line 4").v0, 10);
+
+        let lines = ValidJsonLines::<Data>::from_request(request(&body), &())
+            .await
+            .expect("infallible")
+            .with_policy(ItemPolicy::MaxErrors(1));
+        let items = collect(lines).await;
+        assert_eq!(items.len(), 3);
+        assert!(matches!(
+            items[2],
+            Err(ItemError {
+                line: 3,
+                kind: ItemErrorKind::Inner(_)
+            })
+        ));
+
+        let lines = ValidJsonLines::<Data>::from_request(request(&body), &())
+            .await
+            .expect("infallible")
+            .with_policy(ItemPolicy::MaxErrors(0));
+        let items = collect(lines).await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().expect("line 1").v0, 5);
+        assert_eq!(items[1].as_ref().expect_err("line 2").line, 2);
+    }
+
+    #[cfg(feature = "garde")]
+    #[tokio::test]
+    async fn garde_json_lines() {
+        use garde::Validate;
+
+        #[derive(Debug, serde::Deserialize, Validate)]
+        struct Data {
+            #[garde(range(min = 5, max = 10))]
+            v0: i32,
+        }
+
+        let lines =
+            GardeJsonLines::<Data>::from_request(request(&[r#"{"v0":0}"#, r#"{"v0":6}"#]), &())
+                .await
+                .expect("infallible")
+                .with_policy(ItemPolicy::SkipAndReport);
+        let items: Vec<_> = lines.collect().await;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(
+            items[0],
+            Err(ItemError {
+                line: 1,
+                kind: ItemErrorKind::Valid(_)
+            })
+        ));
+        assert_eq!(items[1].as_ref().expect("line 2").v0, 6);
+    }
+
+    #[cfg(feature = "validify")]
+    #[tokio::test]
+    async fn validify_json_lines() {
+        use validify::Validate;
+
+        #[derive(Debug, serde::Deserialize, Validate)]
+        struct Data {
+            #[validate(range(min = 5.0, max = 10.0))]
+            v0: i32,
+        }
+
+        let lines = ValidifyJsonLines::<Data>::from_request(
+            request(&[r#"{"v0":7}"#, r#"{"v0":0}"#, r#"{"v0":8}"#]),
+            &(),
+        )
+        .await
+        .expect("infallible");
+        let items: Vec<_> = lines.collect().await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().expect("line 1").v0, 7);
+        assert!(matches!(
+            items[1],
+            Err(ItemError {
+                line: 2,
+                kind: ItemErrorKind::Valid(_)
+            })
+        ));
+    }
+}
@@ -120,6 +120,24 @@ impl<T: validify::Modify> crate::HasModify for Protobuf<T> {
     }
 }
 
+#[cfg(feature = "validify")]
+impl<T> crate::PayloadExtractor for Protobuf<T> {
+    type Payload = T;
+
+    fn get_payload(self) -> Self::Payload {
+        self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Validify + validify::ValidifyPayload> crate::HasValidify for Protobuf<T> {
+    type Validify = T;
+    type PayloadExtractor = Protobuf<T::Payload>;
+    fn from_validify(v: Self::Validify) -> Self {
+        Protobuf(v)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{ValidTest, ValidTestParameter};
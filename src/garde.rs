@@ -2,17 +2,18 @@
 //!
 //! ## Feature
 //!
-//! Enable the `garde` feature to use `Garde<E>`.
+//! Enable the `garde` feature to use `Garde<E>` and `GardeGroup<E, G>`.
 //!
 
 #[cfg(test)]
 pub mod test;
 
-use crate::{HasValidate, ValidationRejection};
+use crate::{HasValidate, ValidationGroup, ValidationRejection};
 use axum::extract::{FromRef, FromRequest, FromRequestParts, Request};
 use axum::http::request::Parts;
 use garde::{Report, Validate};
 use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
 /// # `Garde` data extractor
@@ -69,7 +70,117 @@ where
     }
 }
 
-/// `GardeRejection` is returned when the `Garde` extractor fails.
+/// # `GardeGroup` data extractor
+///
+/// `GardeGroup` validates the inner data with the rules of a validation group (scenario),
+/// selected by the marker type `G`.
+///
+/// Unlike `Garde`, the context is not taken from the state but built by the group marker type,
+/// see [`ValidationGroup`].
+///
+/// ```no_run
+/// use axum::Json;
+/// use axum_valid::{GardeGroup, ValidationGroup};
+/// use garde::Validate;
+/// use serde::Deserialize;
+///
+/// #[derive(PartialEq)]
+/// pub enum Scenario {
+///     Create,
+///     Update,
+/// }
+///
+/// pub struct Create;
+///
+/// impl ValidationGroup for Create {
+///     type Context = Scenario;
+///     fn context() -> Self::Context {
+///         Scenario::Create
+///     }
+/// }
+///
+/// pub struct Update;
+///
+/// impl ValidationGroup for Update {
+///     type Context = Scenario;
+///     fn context() -> Self::Context {
+///         Scenario::Update
+///     }
+/// }
+///
+/// #[derive(Deserialize, Validate)]
+/// #[garde(context(Scenario))]
+/// pub struct User {
+///     #[garde(custom(validate_id))]
+///     pub id: Option<u64>,
+///     #[garde(length(min = 1, max = 32))]
+///     pub name: String,
+/// }
+///
+/// fn validate_id(id: &Option<u64>, scenario: &Scenario) -> garde::Result {
+///     match (scenario, id) {
+///         (Scenario::Create, None) | (Scenario::Update, Some(_)) => Ok(()),
+///         (Scenario::Create, Some(_)) => Err(garde::Error::new("id must be absent")),
+///         (Scenario::Update, None) => Err(garde::Error::new("id is required")),
+///     }
+/// }
+///
+/// async fn create(GardeGroup(Json(user), _): GardeGroup<Json<User>, Create>) {
+///     assert!(user.id.is_none());
+/// }
+///
+/// async fn update(GardeGroup(Json(user), _): GardeGroup<Json<User>, Update>) {
+///     assert!(user.id.is_some());
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GardeGroup<E, G>(pub E, pub PhantomData<G>);
+
+impl<E, G> Deref for GardeGroup<E, G> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E, G> DerefMut for GardeGroup<E, G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display, G> Display for GardeGroup<T, G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E, G> GardeGroup<E, G> {
+    /// Consumes the `GardeGroup` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T, G> aide::OperationInput for GardeGroup<T, G>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+/// `GardeRejection` is returned when the `Garde` or `GardeGroup` extractor fails.
 ///
 pub type GardeRejection<E> = ValidationRejection<Report, E>;
 
@@ -118,6 +229,42 @@ where
     }
 }
 
+impl<State, Extractor, Group> FromRequest<State> for GardeGroup<Extractor, Group>
+where
+    State: Send + Sync,
+    Group: ValidationGroup,
+    Extractor: HasValidate + FromRequest<State>,
+    <Extractor as HasValidate>::Validate: Validate<Context = Group::Context>,
+{
+    type Rejection = GardeRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(GardeRejection::Inner)?;
+        inner.get_validate().validate_with(&Group::context())?;
+        Ok(GardeGroup(inner, PhantomData))
+    }
+}
+
+impl<State, Extractor, Group> FromRequestParts<State> for GardeGroup<Extractor, Group>
+where
+    State: Send + Sync,
+    Group: ValidationGroup,
+    Extractor: HasValidate + FromRequestParts<State>,
+    <Extractor as HasValidate>::Validate: Validate<Context = Group::Context>,
+{
+    type Rejection = GardeRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(GardeRejection::Inner)?;
+        inner.get_validate().validate_with(&Group::context())?;
+        Ok(GardeGroup(inner, PhantomData))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +286,89 @@ mod tests {
         assert_eq!(inner, v.into_inner());
     }
 
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn garde_group() {
+        use axum::body::Body;
+        use axum::http::header::CONTENT_TYPE;
+        use axum::Json;
+
+        #[derive(Debug, PartialEq)]
+        enum Scenario {
+            Create,
+            Update,
+        }
+
+        struct Create;
+
+        impl ValidationGroup for Create {
+            type Context = Scenario;
+            fn context() -> Self::Context {
+                Scenario::Create
+            }
+        }
+
+        struct Update;
+
+        impl ValidationGroup for Update {
+            type Context = Scenario;
+            fn context() -> Self::Context {
+                Scenario::Update
+            }
+        }
+
+        fn validate_id(id: &Option<u64>, scenario: &Scenario) -> garde::Result {
+            match (scenario, id) {
+                (Scenario::Create, None) | (Scenario::Update, Some(_)) => Ok(()),
+                _ => Err(garde::Error::new("id")),
+            }
+        }
+
+        #[derive(Debug, serde::Deserialize, Validate)]
+        #[garde(context(Scenario))]
+        struct Data {
+            #[garde(custom(validate_id))]
+            id: Option<u64>,
+            #[garde(length(min = 1))]
+            name: String,
+        }
+
+        fn request(body: &'static str) -> Request {
+            Request::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .expect("build request")
+        }
+
+        let create = r#"{"name":"test"}"#;
+        let update = r#"{"id":1,"name":"test"}"#;
+
+        let GardeGroup(Json(data), _) =
+            GardeGroup::<Json<Data>, Create>::from_request(request(create), &())
+                .await
+                .expect("valid for create");
+        assert_eq!(data.id, None);
+        assert!(matches!(
+            GardeGroup::<Json<Data>, Create>::from_request(request(update), &()).await,
+            Err(GardeRejection::Valid(_))
+        ));
+
+        let GardeGroup(Json(data), _) =
+            GardeGroup::<Json<Data>, Update>::from_request(request(update), &())
+                .await
+                .expect("valid for update");
+        assert_eq!(data.id, Some(1));
+        assert!(matches!(
+            GardeGroup::<Json<Data>, Update>::from_request(request(create), &()).await,
+            Err(GardeRejection::Valid(_))
+        ));
+        assert!(matches!(
+            GardeGroup::<Json<Data>, Update>::from_request(request(r#"{"id":1,"name":""}"#), &())
+                .await,
+            Err(GardeRejection::Valid(_))
+        ));
+    }
+
     #[test]
     fn display_error() {
         // GardeRejection::Valid Display
@@ -0,0 +1,335 @@
+//! # Support for request headers deserialized into a struct
+//!
+//! ## Feature
+//!
+//! Enable the `headers` feature to use `Valid<Headers<T>>`.
+//!
+//! ## Usage
+//!
+//! 1. Implement `Deserialize` and `Validate` for your data type `T`.
+//!    Each field is read from the header with the same name, with `-` written as `_`,
+//!    so the field `x_client_version` is read from the `x-client-version` header.
+//! 2. In your handler function, use `Valid<Headers<T>>` as some parameter's type.
+//!
+//! Header names are case-insensitive and are matched in lowercase. Header values are deserialized like
+//! query string values, so numbers, booleans, optional fields and any type deserialized from a string are supported.
+//! The values of a repeated header are joined with `, `, as if they had been sent in a single header.
+//!
+//! Headers that can't be deserialized, or whose values are not valid UTF-8, are rejected with `400 Bad Request`,
+//! invalid data is rejected with the standard validation rejection. Only the headers read by the data type are
+//! decoded, so a header it ignores may hold any bytes.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_valid::headers::Headers;
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/headers", post(handler))
+//!     }
+//!
+//!     async fn handler(Valid(Headers(headers)): Valid<Headers<ClientHeaders>>) {
+//!         assert!(headers.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct ClientHeaders {
+//!         #[validate(length(equal = 36))]
+//!         pub x_request_id: String,
+//!         #[validate(length(min = 1, max = 16))]
+//!         pub x_client_version: String,
+//!         #[validate(range(min = 1, max = 10))]
+//!         pub x_priority: Option<u8>,
+//!     }
+//! }
+//!
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_valid::headers::Headers;
+//!     use axum_valid::Garde;
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/headers", post(handler))
+//!     }
+//!
+//!     async fn handler(Garde(Headers(headers)): Garde<Headers<ClientHeaders>>) {
+//!         assert!(headers.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct ClientHeaders {
+//!         #[garde(length(min = 36, max = 36))]
+//!         pub x_request_id: String,
+//!         #[garde(length(min = 1, max = 16))]
+//!         pub x_client_version: String,
+//!         #[garde(range(min = 1, max = 10))]
+//!         pub x_priority: Option<u8>,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::HasValidate;
+#[cfg(feature = "validator")]
+use crate::HasValidateArgs;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use serde::de::value::Error;
+use serde::de::{DeserializeOwned, Error as _};
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+#[cfg(feature = "validator")]
+use validator::ValidateArgs;
+
+/// # `Headers<T>` extractor
+///
+/// Deserializes the request headers into `T`, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Headers<T>(pub T);
+
+impl<T> Deref for Headers<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Headers<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display> Display for Headers<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> Headers<T> {
+    /// Consumes the `Headers` and returns the data within.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned> Headers<T> {
+    /// Deserializes `T` from a header map.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, HeadersRejection> {
+        let mut fields = Vec::with_capacity(headers.keys_len());
+        for name in headers.keys() {
+            // Values of headers that `T` doesn't read may not be valid UTF-8
+            let value = headers
+                .get_all(name)
+                .iter()
+                .map(|value| std::str::from_utf8(value.as_bytes()))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|values| values.join(", "))
+                .map_err(|_| {
+                    Error::custom(format_args!("value of header `{name}` is not valid UTF-8"))
+                });
+            fields.push((name.as_str().replace('-', "_"), value));
+        }
+        crate::pairs::from_fallible_pairs(fields)
+            .map(Headers)
+            .map_err(HeadersRejection)
+    }
+}
+
+crate::pairs::pairs_rejection!(
+    /// Rejection used for `Headers<T>` when the headers can't be deserialized,
+    /// or a header value is not valid UTF-8.
+    HeadersRejection,
+    "headers"
+);
+
+impl<State, T> FromRequestParts<State> for Headers<T>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = HeadersRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _: &State) -> Result<Self, Self::Rejection> {
+        Self::from_headers(&parts.headers)
+    }
+}
+
+impl<T> HasValidate for Headers<T> {
+    type Validate = T;
+    fn get_validate(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<'v, T: ValidateArgs<'v>> HasValidateArgs<'v> for Headers<T> {
+    type ValidateArgs = T;
+    fn get_validate_args(&self) -> &Self::ValidateArgs {
+        &self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Modify> crate::HasModify for Headers<T> {
+    type Modify = T;
+
+    fn get_modify(&mut self) -> &mut Self::Modify {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T> crate::PayloadExtractor for Headers<T> {
+    type Payload = T;
+
+    fn get_payload(self) -> Self::Payload {
+        self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Validify + validify::ValidifyPayload> crate::HasValidify for Headers<T> {
+    type Validify = T;
+    type PayloadExtractor = Headers<T::Payload>;
+
+    fn from_validify(v: Self::Validify) -> Self {
+        Headers(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderValue, StatusCode};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct ClientHeaders {
+        x_request_id: String,
+        x_priority: Option<u8>,
+        accept: Option<String>,
+    }
+
+    #[test]
+    fn from_headers() -> anyhow::Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Request-Id", HeaderValue::from_static("abc"));
+        headers.insert("x-priority", HeaderValue::from_static("3"));
+        headers.append("accept", HeaderValue::from_static("text/html"));
+        headers.append("accept", HeaderValue::from_static("application/json"));
+        headers.insert("user-agent", HeaderValue::from_static("test"));
+
+        let Headers(data) = Headers::<ClientHeaders>::from_headers(&headers)?;
+        assert_eq!(
+            data,
+            ClientHeaders {
+                x_request_id: String::from("abc"),
+                x_priority: Some(3),
+                accept: Some(String::from("text/html, application/json")),
+            }
+        );
+
+        headers.insert("x-priority", HeaderValue::from_static("high"));
+        assert!(Headers::<ClientHeaders>::from_headers(&headers).is_err());
+
+        headers.remove("x-request-id");
+        headers.insert("x-priority", HeaderValue::from_static("3"));
+        assert!(Headers::<ClientHeaders>::from_headers(&headers).is_err());
+
+        headers.insert("x-request-id", HeaderValue::from_static("a+b%20c"));
+        let Headers(data) = Headers::<ClientHeaders>::from_headers(&headers)?;
+        assert_eq!(data.x_request_id, "a+b%20c");
+
+        headers.insert("x-request-id", HeaderValue::from_bytes(b"caf\xe9")?);
+        let rejection = Headers::<ClientHeaders>::from_headers(&headers)
+            .expect_err("x-request-id is not valid UTF-8");
+        assert!(rejection.to_string().contains("x-request-id"));
+
+        headers.insert("x-request-id", HeaderValue::from_static("abc"));
+        headers.insert("user-agent", HeaderValue::from_bytes(b"caf\xe9")?);
+        headers.insert("referer", HeaderValue::from_bytes(b"/caf\xe9")?);
+        let Headers(data) = Headers::<ClientHeaders>::from_headers(&headers)?;
+        assert_eq!(data.x_request_id, "abc");
+        Ok(())
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn valid_headers() -> anyhow::Result<()> {
+        use crate::{Valid, VALIDATION_ERROR_STATUS};
+        use axum::routing::get;
+        use axum::Router;
+        use reqwest::Client;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+        use validator::Validate;
+
+        #[derive(Debug, Deserialize, Validate)]
+        struct Parameters {
+            #[validate(length(min = 1, max = 8))]
+            x_client_version: String,
+            #[validate(range(min = 1, max = 10))]
+            x_priority: Option<u8>,
+        }
+
+        async fn handler(Valid(Headers(parameters)): Valid<Headers<Parameters>>) -> String {
+            parameters.x_client_version
+        }
+
+        let router = Router::new().route("/", get(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        let response = client
+            .get(&url)
+            .header("x-client-version", "1.2.0")
+            .header("x-priority", "2")
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "1.2.0");
+
+        let response = client
+            .get(&url)
+            .header("x-client-version", "1.2.0")
+            .header("x-priority", "20")
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+
+        let response = client.get(&url).send().await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+}
@@ -0,0 +1,551 @@
+//! # Validation of top-level sequences and maps
+//!
+//! ## Feature
+//!
+//! Enable the `items` feature to use `Valid<Json<Items<Vec<T>>>>`.
+//!
+//! ## Usage
+//!
+//! 1. Implement `Deserialize` and `Validate` for your item type `T`.
+//! 2. In your handler function, use `Valid<Json<Items<Vec<T>>>>` (or `Garde`, `Validated`, and any other serde based
+//!    extractor) as some parameter's type. `HashMap<K, T>` and `BTreeMap<K, T>` are supported as well.
+//! 3. Optionally, limit the number of items by implementing `LengthRules` for a marker type `L`
+//!    and using `Items<Vec<T>, L>`.
+//!
+//! `Items` is (de)serialized exactly like the collection within, so the wire format doesn't change.
+//!
+//! Errors of each item are reported under its index, or its key for maps, e.g. `0.v0` or `alice.v0`.
+//! A length violation is reported as a `length` error of the collection itself: under `__all__` with validator,
+//! at the root path with garde, and as a schema error with validify.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(all(feature = "validator", feature = "json"))]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::{Json, Router};
+//!     use axum_valid::items::{Items, LengthRules};
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/items", post(handler))
+//!     }
+//!
+//!     pub struct Batch;
+//!
+//!     impl LengthRules for Batch {
+//!         const MIN: usize = 1;
+//!         const MAX: Option<usize> = Some(100);
+//!     }
+//!
+//!     async fn handler(Valid(Json(items)): Valid<Json<Items<Vec<Item>, Batch>>>) {
+//!         assert!(items.iter().all(|item| item.validate().is_ok()));
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Item {
+//!         #[validate(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! #[cfg(all(feature = "garde", feature = "json"))]
+//! mod garde_example {
+//!     use axum::routing::post;
+//!     use axum::{Json, Router};
+//!     use axum_valid::items::Items;
+//!     use axum_valid::Garde;
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!     use std::collections::HashMap;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/items", post(handler))
+//!     }
+//!
+//!     async fn handler(Garde(Json(items)): Garde<Json<Items<HashMap<String, Item>>>>) {
+//!         assert!(items.values().all(|item| item.validate().is_ok()));
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Item {
+//!         #[garde(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(all(feature = "validator", feature = "json"))]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(all(feature = "garde", feature = "json"))]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// Trait for marker types that limit the number of items.
+///
+/// The number of items is unlimited by default.
+pub trait LengthRules {
+    /// Minimum number of items
+    const MIN: usize = 0;
+    /// Maximum number of items
+    const MAX: Option<usize> = None;
+}
+
+/// `LengthRules` without any limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnyLength;
+
+impl LengthRules for AnyLength {}
+
+/// A collection whose items can be validated by `Items`.
+pub trait Collection {
+    /// Type of the items
+    type Item;
+
+    /// Returns the number of items.
+    fn count(&self) -> usize;
+
+    /// Returns the items with their index or key, as used in error paths.
+    fn entries(&self) -> Vec<(String, &Self::Item)>;
+
+    /// Returns mutable references to the items.
+    fn items_mut(&mut self) -> Vec<&mut Self::Item>;
+}
+
+impl<T> Collection for Vec<T> {
+    type Item = T;
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn entries(&self) -> Vec<(String, &T)> {
+        self.iter()
+            .enumerate()
+            .map(|(index, item)| (index.to_string(), item))
+            .collect()
+    }
+
+    fn items_mut(&mut self) -> Vec<&mut T> {
+        self.iter_mut().collect()
+    }
+}
+
+impl<K: Display, T, S: BuildHasher> Collection for HashMap<K, T, S> {
+    type Item = T;
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn entries(&self) -> Vec<(String, &T)> {
+        self.iter()
+            .map(|(key, item)| (key.to_string(), item))
+            .collect()
+    }
+
+    fn items_mut(&mut self) -> Vec<&mut T> {
+        self.values_mut().collect()
+    }
+}
+
+impl<K: Display, T> Collection for BTreeMap<K, T> {
+    type Item = T;
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn entries(&self) -> Vec<(String, &T)> {
+        self.iter()
+            .map(|(key, item)| (key.to_string(), item))
+            .collect()
+    }
+
+    fn items_mut(&mut self) -> Vec<&mut T> {
+        self.values_mut().collect()
+    }
+}
+
+/// # `Items<C, L>` wrapper
+///
+/// Validates every item of the collection `C` and its length against `L`,
+/// see the [module documentation](self).
+pub struct Items<C, L = AnyLength>(pub C, pub PhantomData<L>);
+
+impl<C, L> Items<C, L> {
+    /// Consumes the `Items` and returns the collection within.
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C: Collection, L: LengthRules> Items<C, L> {
+    /// Returns the length violation, if any.
+    pub fn length_violation(&self) -> Option<LengthViolation> {
+        let len = self.0.count();
+        if len < L::MIN {
+            return Some(LengthViolation::Min { min: L::MIN });
+        }
+        match L::MAX {
+            Some(max) if len > max => Some(LengthViolation::Max { max }),
+            _ => None,
+        }
+    }
+}
+
+impl<C, L> Deref for Items<C, L> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C, L> DerefMut for Items<C, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<C: Debug, L> Debug for Items<C, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Items").field(&self.0).finish()
+    }
+}
+
+impl<C: Clone, L> Clone for Items<C, L> {
+    fn clone(&self) -> Self {
+        Items(self.0.clone(), PhantomData)
+    }
+}
+
+impl<C: Default, L> Default for Items<C, L> {
+    fn default() -> Self {
+        Items(C::default(), PhantomData)
+    }
+}
+
+impl<C, L> From<C> for Items<C, L> {
+    fn from(collection: C) -> Self {
+        Items(collection, PhantomData)
+    }
+}
+
+impl<'de, C: Deserialize<'de>, L> Deserialize<'de> for Items<C, L> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        C::deserialize(deserializer).map(|collection| Items(collection, PhantomData))
+    }
+}
+
+impl<C: Serialize, L> Serialize for Items<C, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Violation of `LengthRules`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthViolation {
+    /// There are fewer items than `LengthRules::MIN`.
+    Min {
+        /// Minimum number of items
+        min: usize,
+    },
+    /// There are more items than `LengthRules::MAX`.
+    Max {
+        /// Maximum number of items
+        max: usize,
+    },
+}
+
+impl Display for LengthViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LengthViolation::Min { min } => write!(f, "fewer than {min} items"),
+            LengthViolation::Max { max } => write!(f, "more than {max} items"),
+        }
+    }
+}
+
+impl std::error::Error for LengthViolation {}
+
+#[cfg(feature = "validator")]
+impl<C, L> validator::Validate for Items<C, L>
+where
+    C: Collection,
+    C::Item: validator::Validate,
+    L: LengthRules,
+{
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        let mut errors = validator::ValidationErrors::new();
+        if let Some(violation) = self.length_violation() {
+            let mut error = validator::ValidationError::new("length")
+                .with_message(violation.to_string().into());
+            match violation {
+                LengthViolation::Min { min } => error.add_param("min".into(), &min),
+                LengthViolation::Max { max } => error.add_param("max".into(), &max),
+            }
+            errors.add("__all__", error);
+        }
+        for (key, item) in self.0.entries() {
+            if let Err(item_errors) = item.validate() {
+                errors.0.insert(
+                    key.into(),
+                    validator::ValidationErrorsKind::Struct(Box::new(item_errors)),
+                );
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<C, L> garde::Validate for Items<C, L>
+where
+    C: Collection,
+    C::Item: garde::Validate,
+    L: LengthRules,
+{
+    type Context = <C::Item as garde::Validate>::Context;
+
+    fn validate_into(
+        &self,
+        ctx: &Self::Context,
+        parent: &mut dyn FnMut() -> garde::Path,
+        report: &mut garde::Report,
+    ) {
+        if let Some(violation) = self.length_violation() {
+            report.append(parent(), garde::Error::new(violation.to_string()));
+        }
+        for (key, item) in self.0.entries() {
+            item.validate_into(ctx, &mut || parent().join(key.as_str()), report);
+        }
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<C, L> validify::Validate for Items<C, L>
+where
+    C: Collection,
+    C::Item: validify::Validate,
+    L: LengthRules,
+{
+    fn validate(&self) -> Result<(), validify::ValidationErrors> {
+        let mut errors = validify::ValidationErrors::new();
+        if let Some(violation) = self.length_violation() {
+            let error =
+                validify::ValidationError::new_schema("length").with_message(violation.to_string());
+            errors.add(match violation {
+                LengthViolation::Min { min } => error.with_param("min", &min),
+                LengthViolation::Max { max } => error.with_param("max", &max),
+            });
+        }
+        for (key, item) in self.0.entries() {
+            if let Err(mut item_errors) = item.validate() {
+                for error in item_errors.errors_mut() {
+                    error.set_location(&key);
+                }
+                errors.merge(item_errors);
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<C, L> validify::Modify for Items<C, L>
+where
+    C: Collection,
+    C::Item: validify::Modify,
+{
+    fn modify(&mut self) {
+        for item in self.0.items_mut() {
+            item.modify();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Pair;
+
+    impl LengthRules for Pair {
+        const MIN: usize = 1;
+        const MAX: Option<usize> = Some(2);
+    }
+
+    #[test]
+    fn length_violation() -> anyhow::Result<()> {
+        let items: Items<Vec<i32>, Pair> = serde_json::from_str("[]")?;
+        assert_eq!(
+            items.length_violation(),
+            Some(LengthViolation::Min { min: 1 })
+        );
+        let items: Items<Vec<i32>, Pair> = serde_json::from_str("[1, 2, 3]")?;
+        assert_eq!(
+            items.length_violation(),
+            Some(LengthViolation::Max { max: 2 })
+        );
+        let items: Items<Vec<i32>, Pair> = serde_json::from_str("[1, 2]")?;
+        assert_eq!(items.length_violation(), None);
+        assert_eq!(serde_json::to_string(&items)?, "[1,2]");
+        Ok(())
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn validator_items() -> anyhow::Result<()> {
+        use validator::Validate;
+
+        #[derive(Deserialize, Validate)]
+        struct Item {
+            #[validate(range(min = 5, max = 10))]
+            v0: i32,
+        }
+
+        let items: Items<Vec<Item>, Pair> = serde_json::from_str(r#"[{"v0":5},{"v0":11}]"#)?;
+        let errors = items
+            .validate()
+            .err()
+            .ok_or_else(|| anyhow::anyhow!("valid"))?;
+        assert_eq!(errors.errors().len(), 1);
+        assert!(errors.errors().contains_key("1"));
+
+        let items: Items<HashMap<String, Item>> =
+            serde_json::from_str(r#"{"alice":{"v0":5},"bob":{"v0":4}}"#)?;
+        let errors = items
+            .validate()
+            .err()
+            .ok_or_else(|| anyhow::anyhow!("valid"))?;
+        assert!(errors.errors().contains_key("bob"));
+
+        let items: Items<Vec<Item>, Pair> = serde_json::from_str("[]")?;
+        let errors = items
+            .validate()
+            .err()
+            .ok_or_else(|| anyhow::anyhow!("valid"))?;
+        assert_eq!(errors.field_errors()["__all__"][0].code, "length");
+        Ok(())
+    }
+
+    #[cfg(feature = "garde")]
+    #[test]
+    fn garde_items() -> anyhow::Result<()> {
+        use garde::Validate;
+
+        #[derive(Deserialize, Validate)]
+        struct Item {
+            #[garde(range(min = 5, max = 10))]
+            v0: i32,
+        }
+
+        let items: Items<BTreeMap<String, Item>, Pair> =
+            serde_json::from_str(r#"{"alice":{"v0":5},"bob":{"v0":4},"carol":{"v0":6}}"#)?;
+        let report = items
+            .validate()
+            .err()
+            .ok_or_else(|| anyhow::anyhow!("valid"))?;
+        let paths: Vec<String> = report.iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(paths, vec!["", "bob.v0"]);
+        Ok(())
+    }
+
+    #[cfg(feature = "validify")]
+    #[test]
+    fn validify_items() -> anyhow::Result<()> {
+        use validify::{Modify, Validate, Validify};
+
+        #[derive(Debug, Clone, Deserialize, Validify)]
+        struct Item {
+            #[modify(trim)]
+            #[validate(length(min = 1))]
+            v1: String,
+        }
+
+        let mut items: Items<Vec<Item>> = serde_json::from_str(r#"[{"v1":" a "},{"v1":"  "}]"#)?;
+        items.modify();
+        assert_eq!(items[0].v1, "a");
+        let errors = items
+            .validate()
+            .err()
+            .ok_or_else(|| anyhow::anyhow!("valid"))?;
+        assert_eq!(errors.errors().len(), 1);
+        assert_eq!(errors.errors()[0].location(), "/1/v1");
+        Ok(())
+    }
+
+    #[cfg(all(feature = "validator", feature = "json"))]
+    #[tokio::test]
+    async fn valid_json_items() -> anyhow::Result<()> {
+        use crate::{Valid, VALIDATION_ERROR_STATUS};
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+        use validator::Validate;
+
+        #[derive(Deserialize, Serialize, Validate)]
+        struct Item {
+            #[validate(range(min = 5, max = 10))]
+            v0: i32,
+        }
+
+        async fn handler(Valid(Json(items)): Valid<Json<Items<Vec<Item>, Pair>>>) -> String {
+            items.len().to_string()
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        let client = reqwest::Client::new();
+
+        let response = client
+            .post(&url)
+            .json(&[Item { v0: 5 }, Item { v0: 6 }])
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "2");
+
+        let response = client.post(&url).json(&[Item { v0: 1 }]).send().await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        Ok(())
+    }
+}
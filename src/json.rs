@@ -4,6 +4,8 @@
 //!
 //! Enable the `json` feature (enabled by default) to use `Valid<Json<T>>`.
 //!
+//! Enable the `patch` feature to validate JSON merge-patch bodies, see [`patch`].
+//!
 //! ## Usage
 //!
 //! 1. Implement `Deserialize` and `Validate` for your data type `T`.
@@ -85,6 +87,9 @@
 //! # }
 //! ```
 
+#[cfg(feature = "patch")]
+pub mod patch;
+
 use crate::HasValidate;
 #[cfg(feature = "validator")]
 use crate::HasValidateArgs;
@@ -0,0 +1,756 @@
+//! # Partial validation of JSON merge-patch bodies
+//!
+//! ## Feature
+//!
+//! Enable the `patch` feature to use `ValidPatch<Json<P>>` (with `validator`) and `GardePatch<Json<P>>` (with `garde`).
+//!
+//! ## Usage
+//!
+//! 1. Define a patch type `P` with all-`Option` fields, and implement `Deserialize` for it.
+//! 2. Implement `Patch` for `P`, pointing at the full type `T`, which implements `Default`, `Serialize`, `Deserialize`
+//!    and `Validate`.
+//! 3. In your handler function, use `ValidPatch<Json<P>>` or `GardePatch<Json<P>>` as some parameter's type.
+//!
+//! The body must be a JSON object. It is merged into `T::default()` as described in RFC 7396:
+//! nested objects are merged recursively, and any other value replaces the default one.
+//! The result is validated with the rules of `T`, and only errors of the supplied fields are reported,
+//! e.g. `{"address":{"city":"Paris"}}` reports errors of `address.city`, but not of `address.zip`.
+//! Fields set to `null` (removed by the patch) keep their default value and are not validated.
+//!
+//! Errors are matched to the supplied fields by their JSON paths,
+//! so the field names used in validation errors should be the same as the JSON keys.
+//! Bodies that can't be applied are rejected with [`PatchRejection`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::routing::patch;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::{Patch, ValidPatch};
+//!     use serde::{Deserialize, Serialize};
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/json", patch(handler))
+//!     }
+//!
+//!     async fn handler(ValidPatch(Json(patch)): ValidPatch<Json<ParameterPatch>>) {
+//!         if let Some(v0) = patch.v0 {
+//!             assert!((5..=10).contains(&v0));
+//!         }
+//!     }
+//!
+//!     #[derive(Validate, Serialize, Deserialize, Default)]
+//!     pub struct Parameter {
+//!         #[validate(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//!
+//!     #[derive(Deserialize)]
+//!     pub struct ParameterPatch {
+//!         pub v0: Option<i32>,
+//!         pub v1: Option<String>,
+//!     }
+//!
+//!     impl Patch for ParameterPatch {
+//!         type Target = Parameter;
+//!     }
+//! }
+//!
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::routing::patch;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::{GardePatch, Patch};
+//!     use serde::{Deserialize, Serialize};
+//!     use garde::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/json", patch(handler))
+//!     }
+//!
+//!     async fn handler(GardePatch(Json(patch)): GardePatch<Json<ParameterPatch>>) {
+//!         if let Some(v0) = patch.v0 {
+//!             assert!((5..=10).contains(&v0));
+//!         }
+//!     }
+//!
+//!     #[derive(Validate, Serialize, Deserialize, Default)]
+//!     pub struct Parameter {
+//!         #[garde(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//!
+//!     #[derive(Deserialize)]
+//!     pub struct ParameterPatch {
+//!         pub v0: Option<i32>,
+//!         pub v1: Option<String>,
+//!     }
+//!
+//!     impl Patch for ParameterPatch {
+//!         type Target = Parameter;
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+use axum::extract::rejection::JsonRejection;
+#[cfg(any(feature = "validator", feature = "garde"))]
+use axum::extract::{FromRequest, Request};
+#[cfg(any(feature = "validator", feature = "garde"))]
+use axum::http::StatusCode;
+#[cfg(any(feature = "validator", feature = "garde"))]
+use axum::response::{IntoResponse, Response};
+#[cfg(any(feature = "validator", feature = "garde"))]
+use axum::Json;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+#[cfg(any(feature = "validator", feature = "garde"))]
+use serde_json::Value;
+#[cfg(any(feature = "validator", feature = "garde"))]
+use std::collections::HashSet;
+#[cfg(any(feature = "validator", feature = "garde"))]
+use std::fmt::{Display, Formatter};
+#[cfg(any(feature = "validator", feature = "garde"))]
+use std::ops::{Deref, DerefMut};
+
+/// Trait for patch types, whose supplied fields are validated with the rules of a full type.
+///
+pub trait Patch: DeserializeOwned {
+    /// The full type, whose default value the supplied fields are merged into before validation
+    type Target: Default + Serialize + DeserializeOwned;
+}
+
+/// `PatchRejection` is returned when a JSON merge-patch body can't be applied.
+#[cfg(any(feature = "validator", feature = "garde"))]
+#[derive(Debug)]
+pub enum PatchRejection {
+    /// The body is not valid JSON, see `JsonRejection`.
+    Json(JsonRejection),
+    /// The body is not a JSON object.
+    NotAnObject,
+    /// The body can't be deserialized into the patch type, or the merged value into the full type.
+    Data(serde_json::Error),
+    /// The default value of the full type can't be serialized into a JSON value.
+    Target(serde_json::Error),
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+impl Display for PatchRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchRejection::Json(rejection) => write!(f, "{rejection}"),
+            PatchRejection::NotAnObject => write!(f, "JSON merge-patch body must be an object"),
+            PatchRejection::Data(error) => write!(
+                f,
+                "Failed to deserialize the JSON merge-patch body into the target type: {error}"
+            ),
+            PatchRejection::Target(error) => {
+                write!(
+                    f,
+                    "Failed to serialize the default value of the target type: {error}"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+impl std::error::Error for PatchRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PatchRejection::Json(rejection) => Some(rejection),
+            PatchRejection::NotAnObject => None,
+            PatchRejection::Data(error) | PatchRejection::Target(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+impl IntoResponse for PatchRejection {
+    fn into_response(self) -> Response {
+        match self {
+            PatchRejection::Json(rejection) => rejection.into_response(),
+            PatchRejection::NotAnObject | PatchRejection::Data(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()).into_response()
+            }
+            PatchRejection::Target(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+impl From<JsonRejection> for PatchRejection {
+    fn from(rejection: JsonRejection) -> Self {
+        PatchRejection::Json(rejection)
+    }
+}
+
+/// A patch, the full type built from it, and the paths of the fields it supplies.
+#[cfg(any(feature = "validator", feature = "garde"))]
+struct Patched<P: Patch> {
+    patch: P,
+    target: P::Target,
+    fields: HashSet<String>,
+}
+
+/// Merges `patch` into `target` as described in RFC 7396, recording the paths of the supplied values in `fields`.
+///
+/// Members set to `null` are left as they are in `target`, which is the default value of the full type.
+#[cfg(any(feature = "validator", feature = "garde"))]
+fn merge(target: &mut Value, patch: Value, path: &str, fields: &mut HashSet<String>) {
+    let Value::Object(patch) = patch else {
+        fields.insert(path.to_owned());
+        *target = patch;
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                continue;
+            }
+            let path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{path}.{key}")
+            };
+            merge(
+                target.entry(key).or_insert(Value::Null),
+                value,
+                &path,
+                fields,
+            );
+        }
+    }
+}
+
+/// Returns `true` if `path` is `ancestor` or lies under it, e.g. `address.city` under `address`.
+#[cfg(any(feature = "validator", feature = "garde"))]
+fn is_within(path: &str, ancestor: &str) -> bool {
+    ancestor.is_empty()
+        || path
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+/// Returns `true` if the error at `path` (such as `address.city` or `items[0]`) concerns a supplied field.
+#[cfg(any(feature = "validator", feature = "garde"))]
+fn is_supplied(fields: &HashSet<String>, path: &str) -> bool {
+    fields.iter().any(|field| is_within(path, field))
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+impl<P: Patch> Patched<P> {
+    async fn from_request<State>(req: Request, state: &State) -> Result<Self, PatchRejection>
+    where
+        State: Send + Sync,
+    {
+        let Json(value) = Json::<Value>::from_request(req, state).await?;
+        if !value.is_object() {
+            return Err(PatchRejection::NotAnObject);
+        }
+        let patch = P::deserialize(&value).map_err(PatchRejection::Data)?;
+
+        let mut fields = HashSet::new();
+        let mut target =
+            serde_json::to_value(P::Target::default()).map_err(PatchRejection::Target)?;
+        merge(&mut target, value, "", &mut fields);
+        let target = serde_json::from_value(target).map_err(PatchRejection::Data)?;
+
+        Ok(Patched {
+            patch,
+            target,
+            fields,
+        })
+    }
+}
+
+/// # `ValidPatch` data extractor
+///
+/// `ValidPatch` validates the supplied fields of a JSON merge-patch body
+/// with the `validator` rules of the full type, see [`Patch`].
+///
+#[cfg(feature = "validator")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidPatch<E>(pub E);
+
+#[cfg(feature = "validator")]
+impl<E> Deref for ValidPatch<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<E> DerefMut for ValidPatch<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<T: Display> Display for ValidPatch<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<E> ValidPatch<E> {
+    /// Consumes the `ValidPatch` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(all(feature = "validator", feature = "aide"))]
+impl<T> aide::OperationInput for ValidPatch<T>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+/// Removes the errors of `errors`, found at `prefix`, which don't concern a supplied field.
+///
+/// Struct-level errors are kept if any field of the struct is supplied.
+#[cfg(feature = "validator")]
+fn retain_supplied(
+    errors: &mut validator::ValidationErrors,
+    prefix: &str,
+    fields: &HashSet<String>,
+) {
+    use validator::ValidationErrorsKind;
+
+    errors.errors_mut().retain(|field, kind| {
+        if field == "__all__" {
+            return fields.iter().any(|supplied| is_within(supplied, prefix));
+        }
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{prefix}.{field}")
+        };
+        match kind {
+            ValidationErrorsKind::Field(_) => is_supplied(fields, &path),
+            ValidationErrorsKind::Struct(errors) => {
+                retain_supplied(errors, &path, fields);
+                !errors.is_empty()
+            }
+            ValidationErrorsKind::List(list) => {
+                list.retain(|index, errors| {
+                    retain_supplied(errors, &format!("{path}[{index}]"), fields);
+                    !errors.is_empty()
+                });
+                !list.is_empty()
+            }
+        }
+    });
+}
+
+#[cfg(feature = "validator")]
+impl<State, P> FromRequest<State> for ValidPatch<Json<P>>
+where
+    State: Send + Sync,
+    P: Patch,
+    P::Target: validator::Validate,
+{
+    type Rejection = crate::ValidRejection<PatchRejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        use validator::Validate;
+
+        let Patched {
+            patch,
+            target,
+            fields,
+        } = Patched::<P>::from_request(req, state)
+            .await
+            .map_err(crate::ValidRejection::Inner)?;
+        if let Err(mut errors) = target.validate() {
+            retain_supplied(&mut errors, "", &fields);
+            if !errors.is_empty() {
+                return Err(errors.into());
+            }
+        }
+        Ok(ValidPatch(Json(patch)))
+    }
+}
+
+/// # `GardePatch` data extractor
+///
+/// `GardePatch` validates the supplied fields of a JSON merge-patch body
+/// with the `garde` rules of the full type, see [`Patch`].
+///
+/// Like `Garde`, the context is extracted from the state.
+///
+#[cfg(feature = "garde")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GardePatch<E>(pub E);
+
+#[cfg(feature = "garde")]
+impl<E> Deref for GardePatch<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<E> DerefMut for GardePatch<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<T: Display> Display for GardePatch<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<E> GardePatch<E> {
+    /// Consumes the `GardePatch` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(all(feature = "garde", feature = "aide"))]
+impl<T> aide::OperationInput for GardePatch<T>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<State, P, Context> FromRequest<State> for GardePatch<Json<P>>
+where
+    State: Send + Sync,
+    Context: Send + Sync + axum::extract::FromRef<State>,
+    P: Patch,
+    P::Target: garde::Validate<Context = Context>,
+{
+    type Rejection = crate::GardeRejection<PatchRejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        use garde::Validate;
+
+        let context: Context = axum::extract::FromRef::from_ref(state);
+        let Patched {
+            patch,
+            target,
+            fields,
+        } = Patched::<P>::from_request(req, state)
+            .await
+            .map_err(crate::GardeRejection::Inner)?;
+        if let Err(report) = target.validate_with(&context) {
+            let mut filtered = garde::Report::new();
+            for (path, error) in report.into_inner() {
+                if is_supplied(&fields, &path.to_string()) {
+                    filtered.append(path, error);
+                }
+            }
+            if !filtered.is_empty() {
+                return Err(filtered.into());
+            }
+        }
+        Ok(GardePatch(Json(patch)))
+    }
+}
+
+#[cfg(all(test, any(feature = "validator", feature = "garde")))]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::header::CONTENT_TYPE;
+    use serde::Deserialize;
+    #[cfg(feature = "validator")]
+    use validator::Validate as _;
+
+    fn request(body: &'static str) -> Request {
+        Request::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .expect("build request")
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "validator", derive(validator::Validate))]
+    #[cfg_attr(feature = "garde", derive(garde::Validate))]
+    struct Parameters {
+        #[cfg_attr(feature = "validator", validate(range(min = 5, max = 10)))]
+        #[cfg_attr(feature = "garde", garde(range(min = 5, max = 10)))]
+        v0: i32,
+        #[cfg_attr(feature = "validator", validate(length(min = 1, max = 10)))]
+        #[cfg_attr(feature = "garde", garde(length(min = 1, max = 10)))]
+        v1: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ParametersPatch {
+        v0: Option<i32>,
+        v1: Option<String>,
+    }
+
+    impl Patch for ParametersPatch {
+        type Target = Parameters;
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "validator", derive(validator::Validate))]
+    #[cfg_attr(feature = "garde", derive(garde::Validate))]
+    struct Profile {
+        #[cfg_attr(feature = "validator", validate(length(min = 1)))]
+        #[cfg_attr(feature = "garde", garde(length(min = 1)))]
+        name: String,
+        #[cfg_attr(feature = "validator", validate(nested))]
+        #[cfg_attr(feature = "garde", garde(dive))]
+        address: Address,
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    #[cfg_attr(feature = "validator", derive(validator::Validate))]
+    #[cfg_attr(feature = "garde", derive(garde::Validate))]
+    struct Address {
+        #[cfg_attr(feature = "validator", validate(length(min = 1)))]
+        #[cfg_attr(feature = "garde", garde(length(min = 1)))]
+        city: String,
+        #[cfg_attr(feature = "validator", validate(length(equal = 5)))]
+        #[cfg_attr(feature = "garde", garde(length(equal = 5)))]
+        zip: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ProfilePatch {
+        name: Option<String>,
+        address: Option<AddressPatch>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct AddressPatch {
+        city: Option<String>,
+        zip: Option<String>,
+    }
+
+    impl Patch for ProfilePatch {
+        type Target = Profile;
+    }
+
+    #[tokio::test]
+    async fn patched() {
+        let patched =
+            Patched::<ParametersPatch>::from_request(request(r#"{"v0":6,"v1":null}"#), &())
+                .await
+                .expect("patched");
+        assert_eq!(patched.patch.v0, Some(6));
+        assert_eq!(patched.patch.v1, None);
+        assert_eq!(patched.target.v0, 6);
+        assert_eq!(patched.fields, HashSet::from([String::from("v0")]));
+
+        assert!(matches!(
+            Patched::<ParametersPatch>::from_request(request(r#"{"v0":"6"}"#), &()).await,
+            Err(PatchRejection::Data(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn patched_nested() {
+        let patched = Patched::<ProfilePatch>::from_request(
+            request(r#"{"address":{"city":"Paris","zip":null}}"#),
+            &(),
+        )
+        .await
+        .expect("patched");
+        let address = patched.patch.address.expect("address");
+        assert_eq!(address.city.as_deref(), Some("Paris"));
+        assert_eq!(address.zip, None);
+        assert!(patched.patch.name.is_none());
+        assert_eq!(patched.target.address.city, "Paris");
+        assert_eq!(patched.target.address.zip, "");
+        assert_eq!(
+            patched.fields,
+            HashSet::from([String::from("address.city")])
+        );
+    }
+
+    #[tokio::test]
+    async fn patched_not_an_object() {
+        for body in ["[1]", r#""v0""#, "6", "null"] {
+            let rejection = Patched::<ParametersPatch>::from_request(request(body), &())
+                .await
+                .err()
+                .expect("non-object body should be rejected");
+            assert!(matches!(rejection, PatchRejection::NotAnObject));
+            assert_eq!(
+                rejection.into_response().status(),
+                StatusCode::UNPROCESSABLE_ENTITY
+            );
+        }
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn valid_patch() {
+        let ValidPatch(Json(patch)) =
+            ValidPatch::<Json<ParametersPatch>>::from_request(request(r#"{"v0":6}"#), &())
+                .await
+                .expect("valid patch");
+        assert_eq!(patch.v0, Some(6));
+        assert_eq!(patch.v1, None);
+
+        assert!(
+            ValidPatch::<Json<ParametersPatch>>::from_request(request(r#"{"v1":"v1"}"#), &())
+                .await
+                .is_ok()
+        );
+
+        match ValidPatch::<Json<ParametersPatch>>::from_request(request(r#"{"v0":11}"#), &()).await
+        {
+            Err(crate::ValidRejection::Valid(errors)) => {
+                assert!(errors.errors().contains_key("v0"));
+                assert!(!errors.errors().contains_key("v1"));
+            }
+            _ => panic!("v0 should be invalid"),
+        }
+
+        assert!(matches!(
+            ValidPatch::<Json<ParametersPatch>>::from_request(request("{"), &()).await,
+            Err(crate::ValidRejection::Inner(PatchRejection::Json(_)))
+        ));
+        assert!(matches!(
+            ValidPatch::<Json<ParametersPatch>>::from_request(request("[]"), &()).await,
+            Err(crate::ValidRejection::Inner(PatchRejection::NotAnObject))
+        ));
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn valid_patch_nested() {
+        assert!(ValidPatch::<Json<ProfilePatch>>::from_request(
+            request(r#"{"address":{"city":"Paris"}}"#),
+            &()
+        )
+        .await
+        .is_ok());
+
+        match ValidPatch::<Json<ProfilePatch>>::from_request(
+            request(r#"{"address":{"city":"Paris","zip":"750"}}"#),
+            &(),
+        )
+        .await
+        {
+            Err(crate::ValidRejection::Valid(errors)) => {
+                assert!(!errors.errors().contains_key("name"));
+                let validator::ValidationErrorsKind::Struct(address) = &errors.errors()["address"]
+                else {
+                    panic!("address should have nested errors");
+                };
+                assert!(address.errors().contains_key("zip"));
+                assert!(!address.errors().contains_key("city"));
+            }
+            _ => panic!("address.zip should be invalid"),
+        }
+    }
+
+    #[cfg(feature = "garde")]
+    #[tokio::test]
+    async fn garde_patch() {
+        let GardePatch(Json(patch)) =
+            GardePatch::<Json<ParametersPatch>>::from_request(request(r#"{"v0":6}"#), &())
+                .await
+                .expect("valid patch");
+        assert_eq!(patch.v0, Some(6));
+
+        match GardePatch::<Json<ParametersPatch>>::from_request(request(r#"{"v1":""}"#), &()).await
+        {
+            Err(crate::GardeRejection::Valid(report)) => {
+                assert!(report.iter().all(|(path, _)| path.to_string() == "v1"));
+                assert!(!report.is_empty());
+            }
+            _ => panic!("v1 should be invalid"),
+        }
+    }
+
+    #[cfg(feature = "garde")]
+    #[tokio::test]
+    async fn garde_patch_nested() {
+        assert!(GardePatch::<Json<ProfilePatch>>::from_request(
+            request(r#"{"address":{"city":"Paris"}}"#),
+            &()
+        )
+        .await
+        .is_ok());
+
+        match GardePatch::<Json<ProfilePatch>>::from_request(
+            request(r#"{"address":{"zip":"750"}}"#),
+            &(),
+        )
+        .await
+        {
+            Err(crate::GardeRejection::Valid(report)) => {
+                assert!(!report.is_empty());
+                assert!(report
+                    .iter()
+                    .all(|(path, _)| path.to_string() == "address.zip"));
+            }
+            _ => panic!("address.zip should be invalid"),
+        }
+
+        assert!(matches!(
+            GardePatch::<Json<ProfilePatch>>::from_request(request("[]"), &()).await,
+            Err(crate::GardeRejection::Inner(PatchRejection::NotAnObject))
+        ));
+    }
+}
@@ -0,0 +1,412 @@
+//! # JSON Schema support
+//!
+//! ## Feature
+//!
+//! Enable the `json_schema` feature to use `SchemaValid<E>`.
+//!
+//! ## Usage
+//!
+//! 1. Compile your JSON Schema document into a [`Schema`], and make it available from your state by implementing
+//!    `FromRef<StateType>` for `Schema`.
+//! 2. In your handler function, use `SchemaValid<E>` as some parameter's type, where `E` is an extractor of
+//!    `serde_json::Value`, e.g. `SchemaValid<Json<Value>>`, `SchemaValid<Yaml<Value>>` or `SchemaValid<Form<Value>>`.
+//!
+//! This is useful for payloads without Rust types, whose schema is only known at runtime.
+//! Each violation is reported with the JSON pointer of the offending value (its instance path),
+//! see [`SchemaErrors`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use axum::extract::FromRef;
+//! use axum::routing::post;
+//! use axum::{Json, Router};
+//! use axum_valid::json_schema::{Schema, SchemaValid};
+//! use serde_json::{json, Value};
+//!
+//! #[derive(Clone, FromRef)]
+//! pub struct PluginState {
+//!     schema: Schema,
+//! }
+//!
+//! async fn handler(SchemaValid(Json(payload)): SchemaValid<Json<Value>>) {
+//!     assert!(payload["name"].is_string());
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use tokio::net::TcpListener;
+//! // The schema would usually be loaded from disk.
+//! let schema = Schema::new(&json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "name": { "type": "string", "minLength": 1 },
+//!         "tags": { "type": "array", "items": { "type": "string" } }
+//!     },
+//!     "required": ["name"]
+//! }))?;
+//! let router = Router::new()
+//!     .route("/plugin", post(handler))
+//!     .with_state(PluginState { schema });
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::{HasValidate, ValidationRejection};
+use axum::extract::{FromRef, FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use jsonschema::Validator;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+/// A compiled JSON Schema, cheap to clone and share through state.
+#[derive(Debug, Clone)]
+pub struct Schema(Arc<Validator>);
+
+impl Schema {
+    /// Compiles a JSON Schema document, detecting its draft from `$schema`.
+    pub fn new(schema: &Value) -> Result<Self, jsonschema::ValidationError<'static>> {
+        jsonschema::validator_for(schema).map(Self::from)
+    }
+
+    /// Returns the underlying validator.
+    pub fn validator(&self) -> &Validator {
+        &self.0
+    }
+
+    /// Validates an instance, collecting all violations.
+    pub fn validate(&self, instance: &Value) -> Result<(), SchemaErrors> {
+        let errors: Vec<SchemaError> = self
+            .0
+            .iter_errors(instance)
+            .map(|error| SchemaError {
+                instance_path: error.instance_path().to_string(),
+                schema_path: error.schema_path().to_string(),
+                message: error.to_string(),
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SchemaErrors(errors))
+        }
+    }
+}
+
+impl From<Validator> for Schema {
+    fn from(validator: Validator) -> Self {
+        Self(Arc::new(validator))
+    }
+}
+
+/// A single violation of a JSON Schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// JSON pointer to the invalid value in the payload, empty for the payload itself.
+    pub instance_path: String,
+    /// JSON pointer to the violated keyword in the schema.
+    pub schema_path: String,
+    /// Description of the violation.
+    pub message: String,
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.instance_path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.instance_path, self.message)
+        }
+    }
+}
+
+impl Serialize for SchemaError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("SchemaError", 3)?;
+        state.serialize_field("instance_path", &self.instance_path)?;
+        state.serialize_field("schema_path", &self.schema_path)?;
+        state.serialize_field("message", &self.message)?;
+        state.end()
+    }
+}
+
+/// Violations of a JSON Schema, in the order they were found.
+///
+/// Serialized as an array of objects with `instance_path`, `schema_path` and `message` fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaErrors(pub Vec<SchemaError>);
+
+impl Display for SchemaErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, error) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaErrors {}
+
+impl Serialize for SchemaErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// # `SchemaValid` data extractor
+///
+/// SchemaValid validates a `serde_json::Value` extracted by the inner extractor against the [`Schema`]
+/// taken from the state.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchemaValid<E>(pub E);
+
+impl<E> Deref for SchemaValid<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for SchemaValid<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display> Display for SchemaValid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E> SchemaValid<E> {
+    /// Consumes the `SchemaValid` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T> aide::OperationInput for SchemaValid<T>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+/// `SchemaValidRejection` is returned when the `SchemaValid` extractor fails.
+///
+pub type SchemaValidRejection<E> = ValidationRejection<SchemaErrors, E>;
+
+impl<E> From<SchemaErrors> for SchemaValidRejection<E> {
+    fn from(value: SchemaErrors) -> Self {
+        Self::Valid(value)
+    }
+}
+
+impl<State, Extractor> FromRequest<State> for SchemaValid<Extractor>
+where
+    State: Send + Sync,
+    Schema: FromRef<State>,
+    Extractor: HasValidate<Validate = Value> + FromRequest<State>,
+{
+    type Rejection = SchemaValidRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let schema = Schema::from_ref(state);
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(SchemaValidRejection::Inner)?;
+        schema.validate(inner.get_validate())?;
+        Ok(SchemaValid(inner))
+    }
+}
+
+impl<State, Extractor> FromRequestParts<State> for SchemaValid<Extractor>
+where
+    State: Send + Sync,
+    Schema: FromRef<State>,
+    Extractor: HasValidate<Validate = Value> + FromRequestParts<State>,
+{
+    type Rejection = SchemaValidRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let schema = Schema::from_ref(state);
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(SchemaValidRejection::Inner)?;
+        schema.validate(inner.get_validate())?;
+        Ok(SchemaValid(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::error::Error;
+    use std::io;
+
+    const SCHEMA_VALID: &str = "schema_valid";
+
+    fn schema() -> Schema {
+        Schema::new(&json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 1 },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["name"]
+        }))
+        .expect("valid schema")
+    }
+
+    #[test]
+    fn schema_valid_deref_deref_mut_into_inner() {
+        let mut inner = String::from(SCHEMA_VALID);
+        let mut v = SchemaValid(inner.clone());
+        assert_eq!(&inner, v.deref());
+        inner.push_str(SCHEMA_VALID);
+        v.deref_mut().push_str(SCHEMA_VALID);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[test]
+    fn instance_paths() -> anyhow::Result<()> {
+        let schema = schema();
+        assert!(schema
+            .validate(&json!({ "name": "a", "tags": ["b"] }))
+            .is_ok());
+        assert!(Schema::new(&json!({ "type": 1 })).is_err());
+
+        let errors = schema
+            .validate(&json!({ "name": "", "tags": ["b", 1] }))
+            .expect_err("invalid instance");
+        let mut paths: Vec<_> = errors
+            .0
+            .iter()
+            .map(|error| error.instance_path.as_str())
+            .collect();
+        paths.sort_unstable();
+        assert_eq!(paths, ["/name", "/tags/1"]);
+
+        let errors = schema.validate(&json!({})).expect_err("missing name");
+        assert_eq!(errors.0.len(), 1);
+        assert_eq!(errors.0[0].instance_path, "");
+        assert_eq!(errors.0[0].schema_path, "/required");
+        assert_eq!(errors.to_string(), errors.0[0].message);
+
+        let value = serde_json::to_value(&errors)?;
+        assert_eq!(value[0]["instance_path"], "");
+        assert_eq!(value[0]["schema_path"], "/required");
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn schema_valid_json() -> anyhow::Result<()> {
+        use crate::VALIDATION_ERROR_STATUS;
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use reqwest::Client;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+
+        #[derive(Clone, FromRef)]
+        struct PluginState {
+            schema: Schema,
+        }
+
+        async fn handler(SchemaValid(Json(payload)): SchemaValid<Json<Value>>) -> String {
+            payload["name"].as_str().unwrap_or_default().to_owned()
+        }
+
+        let router = Router::new()
+            .route("/", post(handler))
+            .with_state(PluginState { schema: schema() });
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        let response = client
+            .post(&url)
+            .json(&json!({ "name": "plugin", "tags": ["a"] }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "plugin");
+
+        let response = client
+            .post(&url)
+            .json(&json!({ "name": "plugin", "tags": [1] }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        #[cfg(feature = "into_json")]
+        assert_eq!(
+            response.json::<Value>().await?[0]["instance_path"],
+            "/tags/0"
+        );
+
+        let response = client.post(&url).body("{}").send().await?;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        Ok(())
+    }
+
+    #[test]
+    fn display_error() {
+        fn errors() -> SchemaErrors {
+            schema()
+                .validate(&json!({ "name": 1 }))
+                .expect_err("invalid instance")
+        }
+
+        // SchemaValidRejection::Valid Display
+        let s = errors().to_string();
+        assert!(s.starts_with("/name: "));
+        let vr = SchemaValidRejection::<String>::Valid(errors());
+        assert_eq!(vr.to_string(), s);
+
+        // SchemaValidRejection::Inner Display
+        let inner = String::from(SCHEMA_VALID);
+        let vr = SchemaValidRejection::<String>::Inner(inner.clone());
+        assert_eq!(inner.to_string(), vr.to_string());
+
+        // SchemaValidRejection::Valid Error
+        let vr = SchemaValidRejection::<io::Error>::Valid(errors());
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<SchemaErrors>().is_some())
+        );
+
+        // SchemaValidRejection::Inner Error
+        let vr = SchemaValidRejection::<io::Error>::Inner(io::Error::other(SCHEMA_VALID));
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<io::Error>().is_some())
+        );
+    }
+}
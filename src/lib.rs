@@ -1,25 +1,62 @@
 #![doc = include_str!("../README.md")]
 #![deny(unsafe_code, missing_docs, clippy::unwrap_used)]
 
+pub mod checked;
+#[cfg(all(feature = "validator", any(feature = "garde", feature = "validify")))]
+pub mod composite;
+pub mod content_type;
 #[cfg(feature = "extra")]
 pub mod extra;
 #[cfg(feature = "form")]
 pub mod form;
 #[cfg(feature = "garde")]
 pub mod garde;
+#[cfg(feature = "headers")]
+pub mod headers;
+#[cfg(feature = "items")]
+pub mod items;
 #[cfg(feature = "json")]
 pub mod json;
+#[cfg(feature = "json_schema")]
+pub mod json_schema;
 #[cfg(feature = "msgpack")]
 pub mod msgpack;
+#[cfg(any(feature = "multipart", feature = "typed_multipart"))]
+pub mod multipart;
+#[cfg(feature = "normalize")]
+pub mod normalize;
+#[cfg(feature = "nutype")]
+pub mod nutype;
+#[cfg(any(
+    feature = "headers",
+    feature = "extra_cookie",
+    all(feature = "nutype", any(feature = "query", feature = "form"))
+))]
+mod pairs;
 pub mod path;
 #[cfg(feature = "query")]
 pub mod query;
+#[cfg(feature = "raw_body")]
+pub mod raw_body;
+#[cfg(feature = "serde_valid")]
+pub mod serde_valid;
+#[cfg(feature = "shadow")]
+pub mod shadow;
+#[cfg(feature = "strict")]
+pub mod strict;
 #[cfg(feature = "typed_header")]
 pub mod typed_header;
+pub mod validate_only;
 #[cfg(feature = "validator")]
 pub mod validator;
 #[cfg(feature = "validify")]
 pub mod validify;
+pub mod warning;
+#[cfg(all(
+    feature = "ws",
+    any(feature = "validator", feature = "garde", feature = "validify")
+))]
+pub mod ws;
 #[cfg(feature = "yaml")]
 pub mod yaml;
 
@@ -48,7 +85,7 @@ pub const VALIDATION_ERROR_STATUS: StatusCode = StatusCode::BAD_REQUEST;
 
 /// Trait for types that can supply a reference that can be validated.
 ///
-/// Extractor types `T` that implement this trait can be used with `Valid`, `Garde` or `Validated`.
+/// Extractor types `T` that implement this trait can be used with `Valid`, `Garde`, `Validated` or `Checked`.
 ///
 pub trait HasValidate {
     /// Inner type that can be validated for correctness
@@ -57,11 +94,54 @@ pub trait HasValidate {
     fn get_validate(&self) -> &Self::Validate;
 }
 
+/// Trait for marker types that select a group of validation rules (a scenario).
+///
+/// The same data type is often validated with different rules depending on the scenario,
+/// e.g. `id` must be absent when creating but present when updating.
+///
+/// A group marker type supplies the value passed to validation, as arguments with `ValidGroup`
+/// or as context with `GardeGroup`. Rules can then inspect this value to decide whether they apply.
+///
+pub trait ValidationGroup {
+    /// Type of the value passed to validation
+    type Context;
+    /// Get the value passed to validation
+    fn context() -> Self::Context;
+}
+
 #[cfg(feature = "validator")]
-pub use crate::validator::{HasValidateArgs, Valid, ValidEx, ValidRejection};
+pub use crate::validator::{HasValidateArgs, Valid, ValidEx, ValidGroup, ValidRejection};
 
 #[cfg(feature = "garde")]
-pub use crate::garde::{Garde, GardeRejection};
+pub use crate::garde::{Garde, GardeGroup, GardeRejection};
+
+#[cfg(all(feature = "validator", feature = "garde"))]
+pub use crate::composite::ValidGarde;
+#[cfg(all(feature = "validator", feature = "validify"))]
+pub use crate::composite::ValidValidated;
+#[cfg(all(feature = "validator", any(feature = "garde", feature = "validify")))]
+pub use crate::composite::{CompositeErrors, CompositeRejection};
+
+#[cfg(feature = "serde_valid")]
+pub use crate::serde_valid::{SerdeValid, SerdeValidRejection};
+
+#[cfg(feature = "json_schema")]
+pub use crate::json_schema::{SchemaValid, SchemaValidRejection};
+
+#[cfg(all(feature = "patch", feature = "garde"))]
+pub use crate::json::patch::GardePatch;
+#[cfg(feature = "patch")]
+pub use crate::json::patch::Patch;
+#[cfg(all(feature = "patch", any(feature = "validator", feature = "garde")))]
+pub use crate::json::patch::PatchRejection;
+#[cfg(all(feature = "patch", feature = "validator"))]
+pub use crate::json::patch::ValidPatch;
+
+pub use crate::checked::{Checked, CheckedRejection, Validator};
+pub use crate::validate_only::{
+    PreferValidateOnly, ValidateOnly, ValidateOnlyMode, ValidateOnlyQuery, ValidateOnlyRejection,
+};
+pub use crate::warning::{Advise, Warned, Warnings};
 
 #[cfg(feature = "validify")]
 pub use crate::validify::{
@@ -69,6 +149,40 @@ pub use crate::validify::{
     ValidifyRejection,
 };
 
+/// Derive macro for implementing `HasValidate` on custom extractors.
+///
+/// For structs with a single field, that field is used. Otherwise, mark the field
+/// to validate with `#[axum_valid(inner)]`.
+///
+/// ```
+/// use axum_valid::HasValidate;
+///
+/// #[derive(HasValidate)]
+/// struct MyExtractor<T> {
+///     #[axum_valid(inner)]
+///     data: T,
+///     raw: String,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use axum_valid_derive::HasValidate;
+
+#[cfg(all(feature = "derive", feature = "validator"))]
+pub use axum_valid_derive::HasValidateArgs;
+
+#[cfg(all(feature = "derive", feature = "validify"))]
+pub use axum_valid_derive::{HasModify, HasValidify, PayloadExtractor};
+
+/// Re-exports used by the derive macros, not public API.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "validator")]
+    pub use validator;
+    #[cfg(feature = "validify")]
+    pub use validify;
+}
+
 /// `ValidationRejection` is returned when the validation extractor fails.
 ///
 /// This enumeration captures two types of errors that can occur when using `Valid`: errors related to the validation
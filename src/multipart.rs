@@ -0,0 +1,839 @@
+//! # File constraints for multipart bodies
+//!
+//! ## Feature
+//!
+//! Enable the `multipart` feature to use `ConstrainedMultipart<C>` with axum's raw `Multipart`.
+//!
+//! Enable the `typed_multipart` feature to use `ConstrainedFile<T, C>` as a field of a `TryFromMultipart` type.
+//!
+//! ## Rules
+//!
+//! File constraints are declared by implementing `FileRules` for a marker type `C`:
+//! the maximum file size, allowed content types and file name patterns, and the maximum number of files.
+//! Patterns support the `*` and `?` wildcards and are matched case-insensitively, e.g. `image/*` or `*.png`.
+//!
+//! The constraints are enforced while streaming: the content type and file name are checked before reading the file,
+//! and reading stops as soon as the maximum size is exceeded, without buffering the rest of the file.
+//!
+//! Only fields with a file name are considered files, other fields are not constrained.
+//!
+//! ## Usage
+//!
+//! ### `ConstrainedMultipart<C>`
+//!
+//! Use `ConstrainedMultipart<C>` instead of `Multipart` as some parameter's type, and read the fields
+//! with `next_field`. Violations are returned as `ValidationRejection::Valid(MultipartViolation)`, which is
+//! responded with the same status code and format as the other validation errors.
+//!
+//! ### `ConstrainedFile<T, C>`
+//!
+//! Use `ConstrainedFile<T, C>` instead of `FieldData<T>` as the type of a file field, and validate it
+//! as a nested value: `#[validate(nested)]` with validator, `#[garde(dive)]` with garde, or `#[validate]` with validify.
+//! Violations are then reported as validation errors of this field. To limit the number of files,
+//! use `Vec<ConstrainedFile<T, C>>` with a length rule. If the parent type is validated with a garde context,
+//! pass it as the third parameter, e.g. `ConstrainedFile<T, C, MyContext>`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(all(feature = "multipart", feature = "typed_multipart", feature = "validator"))]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+//!     use axum_valid::multipart::{ConstrainedFile, ConstrainedMultipart, FileRules};
+//!     use axum_valid::{Valid, ValidationRejection};
+//!     use axum::body::Bytes;
+//!     use axum::extract::multipart::MultipartError;
+//!     use axum_valid::multipart::MultipartViolation;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new()
+//!             .route("/raw", post(raw_handler))
+//!             .route("/typed", post(typed_handler))
+//!     }
+//!
+//!     pub struct Image;
+//!
+//!     impl FileRules for Image {
+//!         const MAX_SIZE: Option<usize> = Some(1024 * 1024);
+//!         const CONTENT_TYPES: &'static [&'static str] = &["image/*"];
+//!         const FILE_NAMES: &'static [&'static str] = &["*.png", "*.jpg"];
+//!         const MAX_FILES: Option<usize> = Some(4);
+//!     }
+//!
+//!     async fn raw_handler(
+//!         mut multipart: ConstrainedMultipart<Image>,
+//!     ) -> Result<(), ValidationRejection<MultipartViolation, MultipartError>> {
+//!         while let Some(field) = multipart.next_field().await? {
+//!             let _image: Bytes = field.bytes().await?;
+//!         }
+//!         Ok(())
+//!     }
+//!
+//!     #[derive(TryFromMultipart, Validate)]
+//!     pub struct Upload {
+//!         #[validate(length(min = 1, max = 64))]
+//!         pub title: String,
+//!         #[validate(nested)]
+//!         pub image: ConstrainedFile<Bytes, Image>,
+//!     }
+//!
+//!     async fn typed_handler(Valid(TypedMultipart(upload)): Valid<TypedMultipart<Upload>>) {
+//!         assert!(upload.validate().is_ok());
+//!         assert!(upload.image.get().is_some());
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(all(feature = "multipart", feature = "typed_multipart", feature = "validator"))]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::ValidationRejection;
+use axum::body::Bytes;
+use axum::extract::multipart::{Field, MultipartError, MultipartRejection};
+use axum::extract::{FromRequest, Multipart, Request};
+use axum::http::HeaderMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+
+/// Trait for marker types that declare file constraints.
+///
+/// Every constraint is disabled by default.
+///
+pub trait FileRules {
+    /// Maximum size of each file in bytes
+    const MAX_SIZE: Option<usize> = None;
+    /// Allowed content type patterns, e.g. `image/*`, any content type is allowed if empty
+    const CONTENT_TYPES: &'static [&'static str] = &[];
+    /// Allowed file name patterns, e.g. `*.png`, any file name is allowed if empty
+    const FILE_NAMES: &'static [&'static str] = &[];
+    /// Maximum number of files in a multipart body, only enforced by `ConstrainedMultipart`
+    const MAX_FILES: Option<usize> = None;
+}
+
+/// Violation of a file constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartViolation {
+    /// Name of the field
+    pub field: String,
+    /// Which constraint is violated
+    pub kind: ViolationKind,
+}
+
+/// Kind of `MultipartViolation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The file is larger than `FileRules::MAX_SIZE`.
+    MaxSize {
+        /// Maximum size in bytes
+        max: usize,
+    },
+    /// The content type does not match `FileRules::CONTENT_TYPES`.
+    ContentType {
+        /// Content type of the file
+        content_type: Option<String>,
+    },
+    /// The file name does not match `FileRules::FILE_NAMES`.
+    FileName {
+        /// Name of the file
+        file_name: String,
+    },
+    /// There are more files than `FileRules::MAX_FILES`.
+    MaxFiles {
+        /// Maximum number of files
+        max: usize,
+    },
+}
+
+impl ViolationKind {
+    /// Error code of the violation
+    pub fn code(&self) -> &'static str {
+        match self {
+            ViolationKind::MaxSize { .. } => "max_size",
+            ViolationKind::ContentType { .. } => "content_type",
+            ViolationKind::FileName { .. } => "file_name",
+            ViolationKind::MaxFiles { .. } => "max_files",
+        }
+    }
+}
+
+impl Display for ViolationKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViolationKind::MaxSize { max } => write!(f, "file is larger than {max} bytes"),
+            ViolationKind::ContentType {
+                content_type: Some(content_type),
+            } => write!(f, "content type '{content_type}' is not allowed"),
+            ViolationKind::ContentType { content_type: None } => {
+                write!(f, "content type is missing")
+            }
+            ViolationKind::FileName { file_name } => {
+                write!(f, "file name '{file_name}' is not allowed")
+            }
+            ViolationKind::MaxFiles { max } => write!(f, "more than {max} files"),
+        }
+    }
+}
+
+impl Display for MultipartViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.kind)
+    }
+}
+
+impl std::error::Error for MultipartViolation {}
+
+/// Serialized like the validation errors of validator, e.g.
+/// `{"avatar":[{"code":"max_size","message":"file is larger than 1024 bytes","params":{"max":1024}}]}`.
+#[cfg(feature = "into_json")]
+impl serde::Serialize for MultipartViolation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let params = match &self.kind {
+            ViolationKind::MaxSize { max } | ViolationKind::MaxFiles { max } => {
+                serde_json::json!({ "max": max })
+            }
+            ViolationKind::ContentType { content_type } => {
+                serde_json::json!({ "content_type": content_type })
+            }
+            ViolationKind::FileName { file_name } => serde_json::json!({ "file_name": file_name }),
+        };
+        serde_json::json!({
+            self.field.as_str(): [{
+                "code": self.kind.code(),
+                "message": self.kind.to_string(),
+                "params": params,
+            }]
+        })
+        .serialize(serializer)
+    }
+}
+
+fn essence(content_type: &str) -> &str {
+    content_type.split(';').next().unwrap_or_default().trim()
+}
+
+fn matches(pattern: &[u8], text: &[u8]) -> bool {
+    match (pattern.split_first(), text.split_first()) {
+        (None, _) => text.is_empty(),
+        (Some((b'*', rest)), _) => (0..=text.len()).any(|i| matches(rest, &text[i..])),
+        (Some((b'?', rest)), Some((_, text))) => matches(rest, text),
+        (Some((p, rest)), Some((t, text))) => p.eq_ignore_ascii_case(t) && matches(rest, text),
+        (Some(_), None) => false,
+    }
+}
+
+fn matches_any(patterns: &[&str], text: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| matches(pattern.as_bytes(), text.as_bytes()))
+}
+
+/// Checks the content type and file name of a file field against the rules of `C`.
+fn check_metadata<C: FileRules>(
+    field: &str,
+    file_name: &str,
+    content_type: Option<&str>,
+) -> Result<(), MultipartViolation> {
+    let violation = |kind| MultipartViolation {
+        field: field.to_string(),
+        kind,
+    };
+    if !C::CONTENT_TYPES.is_empty()
+        && !content_type
+            .is_some_and(|content_type| matches_any(C::CONTENT_TYPES, essence(content_type)))
+    {
+        return Err(violation(ViolationKind::ContentType {
+            content_type: content_type.map(String::from),
+        }));
+    }
+    if !C::FILE_NAMES.is_empty() && !matches_any(C::FILE_NAMES, file_name) {
+        return Err(violation(ViolationKind::FileName {
+            file_name: file_name.to_string(),
+        }));
+    }
+    Ok(())
+}
+
+/// Error returned when reading a `ConstrainedMultipart`.
+pub type MultipartFieldRejection = ValidationRejection<MultipartViolation, MultipartError>;
+
+/// # `ConstrainedMultipart` data extractor
+///
+/// `ConstrainedMultipart` wraps axum's `Multipart`, enforcing the file constraints of `C` while the fields are read,
+/// see the [module documentation](self).
+///
+pub struct ConstrainedMultipart<C> {
+    multipart: Multipart,
+    files: usize,
+    _rules: PhantomData<fn() -> C>,
+}
+
+impl<C> Debug for ConstrainedMultipart<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConstrainedMultipart")
+            .field("files", &self.files)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: FileRules> ConstrainedMultipart<C> {
+    /// Consumes the `ConstrainedMultipart` and returns the `Multipart` within.
+    pub fn into_inner(self) -> Multipart {
+        self.multipart
+    }
+
+    /// Yields the next field, checking the number of files, and the content type and file name of file fields.
+    pub async fn next_field(
+        &mut self,
+    ) -> Result<Option<ConstrainedField<'_, C>>, MultipartFieldRejection> {
+        let Some(field) = self
+            .multipart
+            .next_field()
+            .await
+            .map_err(ValidationRejection::Inner)?
+        else {
+            return Ok(None);
+        };
+        let name = field.name().unwrap_or_default().to_string();
+        if let Some(file_name) = field.file_name() {
+            self.files += 1;
+            if let Some(max) = C::MAX_FILES.filter(|max| self.files > *max) {
+                return Err(ValidationRejection::Valid(MultipartViolation {
+                    field: name,
+                    kind: ViolationKind::MaxFiles { max },
+                }));
+            }
+            check_metadata::<C>(&name, file_name, field.content_type())
+                .map_err(ValidationRejection::Valid)?;
+        }
+        Ok(Some(ConstrainedField {
+            field,
+            size: 0,
+            _rules: PhantomData,
+        }))
+    }
+}
+
+impl<State, C> FromRequest<State> for ConstrainedMultipart<C>
+where
+    State: Send + Sync,
+{
+    type Rejection = MultipartRejection;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        Ok(ConstrainedMultipart {
+            multipart: Multipart::from_request(req, state).await?,
+            files: 0,
+            _rules: PhantomData,
+        })
+    }
+}
+
+/// A field of a `ConstrainedMultipart`, enforcing the maximum file size while it is read.
+pub struct ConstrainedField<'a, C> {
+    field: Field<'a>,
+    size: usize,
+    _rules: PhantomData<fn() -> C>,
+}
+
+impl<C> Debug for ConstrainedField<'_, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConstrainedField")
+            .field("field", &self.field)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, C: FileRules> ConstrainedField<'a, C> {
+    /// The field name found in the `Content-Disposition` header.
+    pub fn name(&self) -> Option<&str> {
+        self.field.name()
+    }
+
+    /// The file name found in the `Content-Disposition` header.
+    pub fn file_name(&self) -> Option<&str> {
+        self.field.file_name()
+    }
+
+    /// Get the content type of the field.
+    pub fn content_type(&self) -> Option<&str> {
+        self.field.content_type()
+    }
+
+    /// Get a map of headers as `HeaderMap`.
+    pub fn headers(&self) -> &HeaderMap {
+        self.field.headers()
+    }
+
+    /// Consumes the `ConstrainedField` and returns the `Field` within, without enforcing the maximum size.
+    pub fn into_inner(self) -> Field<'a> {
+        self.field
+    }
+
+    /// Get the next chunk of bytes, failing as soon as the file is larger than the maximum size.
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, MultipartFieldRejection> {
+        let chunk = self
+            .field
+            .chunk()
+            .await
+            .map_err(ValidationRejection::Inner)?;
+        if let Some(chunk) = &chunk {
+            self.size += chunk.len();
+            if let Some(max) = C::MAX_SIZE.filter(|max| self.size > *max) {
+                if self.field.file_name().is_some() {
+                    return Err(ValidationRejection::Valid(MultipartViolation {
+                        field: self.name().unwrap_or_default().to_string(),
+                        kind: ViolationKind::MaxSize { max },
+                    }));
+                }
+            }
+        }
+        Ok(chunk)
+    }
+
+    /// Get the full data of the field, failing as soon as the file is larger than the maximum size.
+    pub async fn bytes(mut self) -> Result<Bytes, MultipartFieldRejection> {
+        let mut bytes = Vec::new();
+        while let Some(chunk) = self.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(Bytes::from(bytes))
+    }
+}
+
+#[cfg(feature = "typed_multipart")]
+pub use self::typed::ConstrainedFile;
+
+#[cfg(feature = "typed_multipart")]
+mod typed {
+    use super::{check_metadata, FileRules, MultipartViolation, ViolationKind};
+    use axum::body::Bytes;
+    use axum::extract::multipart::Field;
+    use axum_typed_multipart::{
+        FieldData, FieldMetadata, TryFromChunks, TryFromField, TypedMultipartError,
+    };
+    use futures_core::Stream;
+    use std::fmt::{Debug, Formatter};
+    use std::marker::PhantomData;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// # `ConstrainedFile` multipart field
+    ///
+    /// `ConstrainedFile` reads a file field of a `TryFromMultipart` type, enforcing the file constraints of `C`,
+    /// see the [module documentation](super).
+    ///
+    /// A violation does not fail the extraction, but is reported when the field is validated.
+    /// With validator, it is reported as a struct-level (`__all__`) error nested under the field name.
+    ///
+    /// `Ctx` is the garde context of the parent type, `()` by default.
+    ///
+    pub struct ConstrainedFile<T, C, Ctx = ()> {
+        result: Result<FieldData<T>, MultipartViolation>,
+        _rules: PhantomData<fn() -> (C, Ctx)>,
+    }
+
+    impl<T: Debug, C, Ctx> Debug for ConstrainedFile<T, C, Ctx> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.debug_tuple("ConstrainedFile")
+                .field(&self.result)
+                .finish()
+        }
+    }
+
+    impl<T, C, Ctx> ConstrainedFile<T, C, Ctx> {
+        fn violated(violation: MultipartViolation) -> Self {
+            ConstrainedFile {
+                result: Err(violation),
+                _rules: PhantomData,
+            }
+        }
+
+        /// Returns the file, `None` if a constraint is violated.
+        pub fn get(&self) -> Option<&FieldData<T>> {
+            self.result.as_ref().ok()
+        }
+
+        /// Returns the violated constraint, if any.
+        pub fn violation(&self) -> Option<&MultipartViolation> {
+            self.result.as_ref().err()
+        }
+
+        /// Consumes the `ConstrainedFile` and returns the file, `None` if a constraint is violated.
+        pub fn into_inner(self) -> Option<FieldData<T>> {
+            self.result.ok()
+        }
+    }
+
+    /// Chunks of a field, ending early once the maximum size is exceeded.
+    struct Limited<'a> {
+        field: Field<'a>,
+        name: String,
+        size: usize,
+        max: Option<usize>,
+        limit_bytes: Option<usize>,
+        exceeded: bool,
+    }
+
+    impl Stream for Limited<'_> {
+        type Item = Result<Bytes, TypedMultipartError>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.get_mut();
+            if this.exceeded {
+                return Poll::Ready(None);
+            }
+            let chunk = match Pin::new(&mut this.field).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => chunk,
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error.into()))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            this.size += chunk.len();
+            if let Some(limit_bytes) = this.limit_bytes.filter(|limit| this.size > *limit) {
+                return Poll::Ready(Some(Err(TypedMultipartError::FieldTooLarge {
+                    field_name: std::mem::take(&mut this.name),
+                    limit_bytes,
+                })));
+            }
+            if this.max.is_some_and(|max| this.size > max) {
+                this.exceeded = true;
+                return Poll::Ready(None);
+            }
+            Poll::Ready(Some(Ok(chunk)))
+        }
+    }
+
+    #[axum_typed_multipart::async_trait]
+    impl<T, C, Ctx> TryFromField for ConstrainedFile<T, C, Ctx>
+    where
+        T: TryFromChunks + Send + Sync,
+        C: FileRules,
+    {
+        async fn try_from_field(
+            field: Field<'_>,
+            limit_bytes: Option<usize>,
+        ) -> Result<Self, TypedMultipartError> {
+            let metadata = FieldMetadata::from(&field);
+            let name = metadata.name.clone().unwrap_or_default();
+            let file_name = metadata.file_name.as_deref().unwrap_or_default();
+            if let Err(violation) =
+                check_metadata::<C>(&name, file_name, metadata.content_type.as_deref())
+            {
+                return Ok(Self::violated(violation));
+            }
+            let mut chunks = Limited {
+                field,
+                name: name.clone(),
+                size: 0,
+                max: C::MAX_SIZE,
+                limit_bytes,
+                exceeded: false,
+            };
+            let contents = T::try_from_chunks(&mut chunks, metadata.clone()).await?;
+            if let (true, Some(max)) = (chunks.exceeded, C::MAX_SIZE) {
+                return Ok(Self::violated(MultipartViolation {
+                    field: name,
+                    kind: ViolationKind::MaxSize { max },
+                }));
+            }
+            Ok(ConstrainedFile {
+                result: Ok(FieldData { metadata, contents }),
+                _rules: PhantomData,
+            })
+        }
+    }
+
+    #[cfg(feature = "validator")]
+    impl<T, C, Ctx> validator::Validate for ConstrainedFile<T, C, Ctx> {
+        fn validate(&self) -> Result<(), validator::ValidationErrors> {
+            let Some(violation) = self.violation() else {
+                return Ok(());
+            };
+            let mut error = validator::ValidationError::new(violation.kind.code())
+                .with_message(violation.kind.to_string().into());
+            match &violation.kind {
+                ViolationKind::MaxSize { max } | ViolationKind::MaxFiles { max } => {
+                    error.add_param("max".into(), max)
+                }
+                ViolationKind::ContentType { content_type } => {
+                    error.add_param("content_type".into(), content_type)
+                }
+                ViolationKind::FileName { file_name } => {
+                    error.add_param("file_name".into(), file_name)
+                }
+            }
+            let mut errors = validator::ValidationErrors::new();
+            errors.add("__all__", error);
+            Err(errors)
+        }
+    }
+
+    #[cfg(feature = "garde")]
+    impl<T, C, Ctx> garde::Validate for ConstrainedFile<T, C, Ctx> {
+        type Context = Ctx;
+
+        fn validate_into(
+            &self,
+            _ctx: &Self::Context,
+            parent: &mut dyn FnMut() -> garde::Path,
+            report: &mut garde::Report,
+        ) {
+            if let Some(violation) = self.violation() {
+                report.append(parent(), garde::Error::new(violation.kind.to_string()));
+            }
+        }
+    }
+
+    #[cfg(feature = "validify")]
+    impl<T, C, Ctx> validify::Validate for ConstrainedFile<T, C, Ctx> {
+        fn validate(&self) -> Result<(), validify::ValidationErrors> {
+            let Some(violation) = self.violation() else {
+                return Ok(());
+            };
+            let mut errors = validify::ValidationErrors::new();
+            errors.add(
+                validify::ValidationError::new_field(violation.kind.code())
+                    .with_message(violation.kind.to_string()),
+            );
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::header::CONTENT_TYPE;
+
+    const BOUNDARY: &str = "axum-valid-boundary";
+
+    struct Image;
+
+    impl FileRules for Image {
+        const MAX_SIZE: Option<usize> = Some(8);
+        const CONTENT_TYPES: &'static [&'static str] = &["image/*"];
+        const FILE_NAMES: &'static [&'static str] = &["*.png"];
+        const MAX_FILES: Option<usize> = Some(2);
+    }
+
+    /// Builds a multipart request from `(name, file name, content type, data)` parts.
+    fn request(parts: &[(&str, Option<&str>, Option<&str>, &str)]) -> Request {
+        let mut body = String::new();
+        for (name, file_name, content_type, data) in parts {
+            body.push_str(&format!("--{BOUNDARY}\r\n"));
+            body.push_str(&format!("Content-Disposition: form-data; name=\"{name}\""));
+            if let Some(file_name) = file_name {
+                body.push_str(&format!("; filename=\"{file_name}\""));
+            }
+            body.push_str("\r\n");
+            if let Some(content_type) = content_type {
+                body.push_str(&format!("Content-Type: {content_type}\r\n"));
+            }
+            body.push_str(&format!("\r\n{data}\r\n"));
+        }
+        body.push_str(&format!("--{BOUNDARY}--\r\n"));
+        Request::builder()
+            .method("POST")
+            .header(
+                CONTENT_TYPE,
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            )
+            .body(Body::from(body))
+            .expect("build request")
+    }
+
+    async fn read_all(req: Request) -> Result<Vec<Bytes>, MultipartFieldRejection> {
+        let mut multipart = ConstrainedMultipart::<Image>::from_request(req, &())
+            .await
+            .expect("multipart");
+        let mut fields = Vec::new();
+        while let Some(field) = multipart.next_field().await? {
+            fields.push(field.bytes().await?);
+        }
+        Ok(fields)
+    }
+
+    fn violation(result: Result<Vec<Bytes>, MultipartFieldRejection>) -> ViolationKind {
+        match result {
+            Err(ValidationRejection::Valid(violation)) => violation.kind,
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn patterns() {
+        assert!(matches_any(&["*.png"], "a.PNG"));
+        assert!(matches_any(&["image/*"], "image/png"));
+        assert!(matches_any(&["file?.txt"], "file1.txt"));
+        assert!(!matches_any(&["file?.txt"], "file10.txt"));
+        assert!(!matches_any(&["*.png"], "a.png.exe"));
+        assert_eq!(essence("text/plain; charset=utf-8"), "text/plain");
+    }
+
+    #[tokio::test]
+    async fn constrained_multipart() {
+        let png = ("image", Some("a.png"), Some("image/png"), "12345678");
+        let fields = read_all(request(&[("title", None, None, "a long title"), png]))
+            .await
+            .expect("valid");
+        assert_eq!(
+            fields,
+            vec![Bytes::from("a long title"), Bytes::from("12345678")]
+        );
+
+        assert_eq!(
+            violation(
+                read_all(request(&[(
+                    "image",
+                    Some("a.png"),
+                    Some("image/png"),
+                    "123456789"
+                )]))
+                .await
+            ),
+            ViolationKind::MaxSize { max: 8 }
+        );
+        assert_eq!(
+            violation(
+                read_all(request(&[(
+                    "image",
+                    Some("a.png"),
+                    Some("text/plain"),
+                    "1"
+                )]))
+                .await
+            ),
+            ViolationKind::ContentType {
+                content_type: Some(String::from("text/plain"))
+            }
+        );
+        assert_eq!(
+            violation(read_all(request(&[("image", Some("a.exe"), Some("image/png"), "1")])).await),
+            ViolationKind::FileName {
+                file_name: String::from("a.exe")
+            }
+        );
+        assert_eq!(
+            violation(read_all(request(&[png, png, png])).await),
+            ViolationKind::MaxFiles { max: 2 }
+        );
+
+        let rejection = read_all(request(&[("image", Some("a.exe"), Some("image/png"), "1")]))
+            .await
+            .expect_err("invalid file name");
+        let response = axum::response::IntoResponse::into_response(rejection);
+        assert_eq!(response.status(), crate::VALIDATION_ERROR_STATUS);
+    }
+
+    #[cfg(all(feature = "typed_multipart", feature = "validator"))]
+    #[tokio::test]
+    async fn constrained_file() {
+        use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+        use validator::Validate;
+
+        #[derive(TryFromMultipart, Validate)]
+        struct Upload {
+            #[validate(nested)]
+            image: ConstrainedFile<Bytes, Image>,
+        }
+
+        let TypedMultipart(upload) = TypedMultipart::<Upload>::from_request(
+            request(&[("image", Some("a.png"), Some("image/png"), "12345678")]),
+            &(),
+        )
+        .await
+        .expect("typed multipart");
+        assert!(upload.validate().is_ok());
+        assert_eq!(
+            upload.image.get().map(|file| file.contents.clone()),
+            Some(Bytes::from("12345678"))
+        );
+
+        let TypedMultipart(upload) = TypedMultipart::<Upload>::from_request(
+            request(&[("image", Some("a.png"), Some("image/png"), "123456789")]),
+            &(),
+        )
+        .await
+        .expect("typed multipart");
+        assert_eq!(
+            upload.image.violation().map(|violation| &violation.kind),
+            Some(&ViolationKind::MaxSize { max: 8 })
+        );
+        let errors = upload.validate().expect_err("too large");
+        let Some(validator::ValidationErrorsKind::Struct(errors)) = errors.errors().get("image")
+        else {
+            panic!("image should have nested errors");
+        };
+        let errors = errors.field_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors["__all__"][0].code, "max_size");
+    }
+
+    #[cfg(all(feature = "typed_multipart", feature = "garde"))]
+    #[tokio::test]
+    async fn garde_constrained_file() {
+        use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+        use garde::Validate;
+
+        struct UploadContext {
+            max_title: usize,
+        }
+
+        fn validate_title(title: &str, ctx: &UploadContext) -> garde::Result {
+            if title.len() > ctx.max_title {
+                return Err(garde::Error::new("title is too long"));
+            }
+            Ok(())
+        }
+
+        #[derive(TryFromMultipart, Validate)]
+        #[garde(context(UploadContext))]
+        struct Upload {
+            #[garde(custom(validate_title))]
+            title: String,
+            #[garde(dive)]
+            image: ConstrainedFile<Bytes, Image, UploadContext>,
+        }
+
+        let ctx = UploadContext { max_title: 4 };
+        let TypedMultipart(upload) = TypedMultipart::<Upload>::from_request(
+            request(&[
+                ("title", None, None, "cat"),
+                ("image", Some("a.png"), Some("image/png"), "12345678"),
+            ]),
+            &(),
+        )
+        .await
+        .expect("typed multipart");
+        assert!(upload.validate_with(&ctx).is_ok());
+
+        let TypedMultipart(upload) = TypedMultipart::<Upload>::from_request(
+            request(&[
+                ("title", None, None, "cat"),
+                ("image", Some("a.exe"), Some("image/png"), "1"),
+            ]),
+            &(),
+        )
+        .await
+        .expect("typed multipart");
+        let report = upload.validate_with(&ctx).expect_err("invalid file name");
+        assert!(report.iter().all(|(path, _)| path.to_string() == "image"));
+        assert!(!report.is_empty());
+    }
+}
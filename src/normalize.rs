@@ -0,0 +1,586 @@
+//! # Normalization before validation
+//!
+//! ## Feature
+//!
+//! Enable the `normalize` feature to use `Normalized<E>`.
+//! Enable the `derive` feature as well to derive `Normalize`.
+//! Enable the `sanitize` feature to sanitize HTML with [`ammonia`](https://docs.rs/ammonia).
+//!
+//! ## Usage
+//!
+//! Trimming or lowercasing inputs before validating them is common, but only `validify` can modify the data.
+//! This module provides a modification step that works with every backend:
+//!
+//! * [`trim`], [`lowercase`], [`collapse_whitespace`], [`nfc`] and [`strip_control`] normalize the strings of a
+//!   [`TextField`], i.e. a `String`, or an `Option` or `Vec` of them.
+//! * [`Normalize`] is implemented by the data to normalize all of its fields. With the `derive` feature, it can be
+//!   derived by listing the operations of each field in `#[normalize(...)]`, and `#[normalize(nested)]` normalizes
+//!   a field implementing `Normalize` itself.
+//! * [`Normalized<E>`] normalizes the data extracted by `E`, which must implement `DerefMut` to the data.
+//!   It implements `HasValidate` as well, so `Valid<Normalized<E>>` or `Garde<Normalized<E>>` normalize the data
+//!   before validating it.
+//!
+//! ### Sanitization
+//!
+//! User-generated rich text must be sanitized before it is stored. With the `sanitize` feature:
+//!
+//! * `sanitize_html` removes the markup not in the default allow-list of `ammonia`, such as scripts,
+//!   event handler attributes and `javascript:` links, and `sanitize_html_with` uses a custom `ammonia::Builder`.
+//! * `strip_html` removes all markup, keeping the text.
+//!
+//! Both are operations like the ones above, so validation limits apply to the sanitized text.
+//! They also work as `validify` modifiers, e.g. `#[modify(custom(axum_valid::normalize::sanitize_html))]`
+//! with `Modified` or `ValidifiedByRef`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(all(feature = "validator", feature = "derive"))]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::normalize::{Normalize, Normalized};
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/json", post(handler))
+//!     }
+//!
+//!     async fn handler(Valid(Normalized(Json(user))): Valid<Normalized<Json<User>>>) {
+//!         assert_eq!(user.email, user.email.trim().to_lowercase());
+//!     }
+//!
+//!     #[derive(Validate, Normalize, Deserialize)]
+//!     pub struct User {
+//!         #[normalize(trim, lowercase)]
+//!         #[validate(email)]
+//!         pub email: String,
+//!         #[normalize(strip_control, collapse_whitespace, nfc)]
+//!         #[validate(length(min = 1, max = 32))]
+//!         pub name: String,
+//!     }
+//! }
+//!
+//! #[cfg(all(feature = "garde", feature = "derive"))]
+//! mod garde_example {
+//!     use axum::routing::post;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::normalize::{Normalize, Normalized};
+//!     use axum_valid::Garde;
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/json", post(handler))
+//!     }
+//!
+//!     async fn handler(Garde(Normalized(Json(user))): Garde<Normalized<Json<User>>>) {
+//!         assert_eq!(user.email, user.email.trim().to_lowercase());
+//!     }
+//!
+//!     #[derive(Validate, Normalize, Deserialize)]
+//!     pub struct User {
+//!         #[normalize(trim, lowercase)]
+//!         #[garde(length(min = 3, max = 64))]
+//!         pub email: String,
+//!         #[normalize(strip_control, collapse_whitespace, nfc)]
+//!         #[garde(length(min = 1, max = 32))]
+//!         pub name: String,
+//!     }
+//! }
+//!
+//! #[cfg(all(feature = "validify", feature = "sanitize"))]
+//! mod validify_example {
+//!     use axum::routing::post;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::ValidifiedByRef;
+//!     use serde::Deserialize;
+//!     use validify::Validify;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/json", post(handler))
+//!     }
+//!
+//!     async fn handler(ValidifiedByRef(Json(comment)): ValidifiedByRef<Json<Comment>>) {
+//!         assert!(!comment.body.contains("<script"));
+//!     }
+//!
+//!     #[derive(Validify, Deserialize)]
+//!     pub struct Comment {
+//!         #[modify(custom(axum_valid::normalize::sanitize_html), trim)]
+//!         #[validate(length(min = 1, max = 1000))]
+//!         pub body: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(all(feature = "validator", feature = "derive"))]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(all(feature = "garde", feature = "derive"))]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     #[cfg(all(feature = "validify", feature = "sanitize"))]
+//! #     let router = router.nest("/validify", validify_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::HasValidate;
+#[cfg(feature = "validator")]
+use crate::HasValidateArgs;
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// Derive macro for implementing `Normalize`, see the [`axum-valid-derive`](axum_valid_derive) documentation.
+#[cfg(feature = "derive")]
+pub use axum_valid_derive::Normalize;
+
+/// Trait for data that can normalize itself before validation.
+pub trait Normalize {
+    /// Normalize the data in place
+    fn normalize(&mut self);
+}
+
+impl<T: Normalize> Normalize for Option<T> {
+    fn normalize(&mut self) {
+        if let Some(value) = self {
+            value.normalize();
+        }
+    }
+}
+
+impl<T: Normalize> Normalize for Vec<T> {
+    fn normalize(&mut self) {
+        self.iter_mut().for_each(Normalize::normalize);
+    }
+}
+
+impl<T: Normalize + ?Sized> Normalize for Box<T> {
+    fn normalize(&mut self) {
+        self.as_mut().normalize();
+    }
+}
+
+/// Trait for field types whose strings can be normalized by the functions of this module.
+pub trait TextField {
+    /// Apply `f` to every string of the field
+    fn for_each_text(&mut self, f: &mut dyn FnMut(&mut String));
+}
+
+impl TextField for String {
+    fn for_each_text(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(self)
+    }
+}
+
+impl<T: TextField> TextField for Option<T> {
+    fn for_each_text(&mut self, f: &mut dyn FnMut(&mut String)) {
+        if let Some(value) = self {
+            value.for_each_text(f);
+        }
+    }
+}
+
+impl<T: TextField> TextField for Vec<T> {
+    fn for_each_text(&mut self, f: &mut dyn FnMut(&mut String)) {
+        for value in self {
+            value.for_each_text(f);
+        }
+    }
+}
+
+/// Removes leading and trailing whitespace.
+pub fn trim<T: TextField + ?Sized>(field: &mut T) {
+    field.for_each_text(&mut |s| {
+        let trimmed = s.trim();
+        if trimmed.len() != s.len() {
+            *s = trimmed.to_owned();
+        }
+    });
+}
+
+/// Converts to lowercase, as defined by `str::to_lowercase`.
+pub fn lowercase<T: TextField + ?Sized>(field: &mut T) {
+    field.for_each_text(&mut |s| {
+        if s.chars().any(char::is_uppercase) {
+            *s = s.to_lowercase();
+        }
+    });
+}
+
+/// Replaces every run of whitespace with a single space, and removes leading and trailing whitespace.
+pub fn collapse_whitespace<T: TextField + ?Sized>(field: &mut T) {
+    field.for_each_text(&mut |s| {
+        *s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    });
+}
+
+/// Converts to Unicode Normalization Form C (canonical composition).
+pub fn nfc<T: TextField + ?Sized>(field: &mut T) {
+    field.for_each_text(&mut |s| {
+        if !is_nfc(s) {
+            *s = s.nfc().collect();
+        }
+    });
+}
+
+/// Removes control characters, including line breaks and tabs.
+pub fn strip_control<T: TextField + ?Sized>(field: &mut T) {
+    field.for_each_text(&mut |s| s.retain(|c| !c.is_control()));
+}
+
+/// Sanitizes HTML with the default allow-list of `ammonia`, see [`ammonia::clean`].
+#[cfg(feature = "sanitize")]
+pub fn sanitize_html<T: TextField + ?Sized>(field: &mut T) {
+    field.for_each_text(&mut |s| *s = ammonia::clean(s));
+}
+
+/// Sanitizes HTML with the allow-list configured in `builder`.
+#[cfg(feature = "sanitize")]
+pub fn sanitize_html_with<T: TextField + ?Sized>(field: &mut T, builder: &ammonia::Builder<'_>) {
+    field.for_each_text(&mut |s| *s = builder.clean(s).to_string());
+}
+
+/// Removes all HTML markup, keeping the text.
+///
+/// The contents of `script` and `style` elements are removed as well, and the result is escaped HTML.
+#[cfg(feature = "sanitize")]
+pub fn strip_html<T: TextField + ?Sized>(field: &mut T) {
+    let builder = ammonia::Builder::empty();
+    sanitize_html_with(field, &builder);
+}
+
+/// # `Normalized` data extractor
+///
+/// `Normalized` normalizes the data extracted by `E` with its [`Normalize`] implementation.
+///
+/// Normalization can't fail, the rejection is the one of the inner extractor.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Normalized<E>(pub E);
+
+impl<E> Deref for Normalized<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for Normalized<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display> Display for Normalized<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E> Normalized<E> {
+    /// Consumes the `Normalized` and returns the normalized data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully normalized.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T> aide::OperationInput for Normalized<T>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+impl<E: HasValidate> HasValidate for Normalized<E> {
+    type Validate = E::Validate;
+    fn get_validate(&self) -> &Self::Validate {
+        self.0.get_validate()
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<'v, E: HasValidateArgs<'v>> HasValidateArgs<'v> for Normalized<E> {
+    type ValidateArgs = E::ValidateArgs;
+    fn get_validate_args(&self) -> &Self::ValidateArgs {
+        self.0.get_validate_args()
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<E: crate::HasModify> crate::HasModify for Normalized<E> {
+    type Modify = E::Modify;
+    fn get_modify(&mut self) -> &mut Self::Modify {
+        self.0.get_modify()
+    }
+}
+
+impl<State, Extractor> FromRequest<State> for Normalized<Extractor>
+where
+    State: Send + Sync,
+    Extractor: FromRequest<State> + DerefMut,
+    Extractor::Target: Normalize,
+{
+    type Rejection = <Extractor as FromRequest<State>>::Rejection;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let mut inner = Extractor::from_request(req, state).await?;
+        inner.deref_mut().normalize();
+        Ok(Normalized(inner))
+    }
+}
+
+impl<State, Extractor> FromRequestParts<State> for Normalized<Extractor>
+where
+    State: Send + Sync,
+    Extractor: FromRequestParts<State> + DerefMut,
+    Extractor::Target: Normalize,
+{
+    type Rejection = <Extractor as FromRequestParts<State>>::Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let mut inner = Extractor::from_request_parts(parts, state).await?;
+        inner.deref_mut().normalize();
+        Ok(Normalized(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NORMALIZED: &str = "normalized";
+
+    #[test]
+    fn normalized_deref_deref_mut_into_inner() {
+        let mut inner = String::from(NORMALIZED);
+        let mut v = Normalized(inner.clone());
+        assert_eq!(&inner, v.deref());
+        inner.push_str(NORMALIZED);
+        v.deref_mut().push_str(NORMALIZED);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[test]
+    fn operations() {
+        let mut s = String::from("  Hello \t\n World  ");
+        trim(&mut s);
+        assert_eq!(s, "Hello \t\n World");
+        strip_control(&mut s);
+        assert_eq!(s, "Hello  World");
+        collapse_whitespace(&mut s);
+        assert_eq!(s, "Hello World");
+        lowercase(&mut s);
+        assert_eq!(s, "hello world");
+
+        // "e" followed by a combining acute accent
+        let mut s = Some(String::from("caf\u{65}\u{301}"));
+        nfc(&mut s);
+        assert_eq!(s.as_deref(), Some("caf\u{e9}"));
+
+        let mut v = vec![String::from(" A "), String::from("b ")];
+        trim(&mut v);
+        lowercase(&mut v);
+        assert_eq!(v, ["a", "b"]);
+
+        let mut none: Option<String> = None;
+        trim(&mut none);
+        assert_eq!(none, None);
+    }
+
+    #[cfg(feature = "sanitize")]
+    #[test]
+    fn sanitize() {
+        let mut s = String::from(
+            r#"<p onclick="steal()">Hi <b>there</b><script>alert(1)</script><a href="javascript:x()">!</a></p>"#,
+        );
+        sanitize_html(&mut s);
+        assert_eq!(
+            s,
+            r#"<p>Hi <b>there</b><a rel="noopener noreferrer">!</a></p>"#
+        );
+
+        let mut s = Some(String::from(
+            "<p>Fish &amp; <i>chips</i><style>p {}</style></p>",
+        ));
+        strip_html(&mut s);
+        assert_eq!(s.as_deref(), Some("Fish &amp; chips"));
+
+        let mut builder = ammonia::Builder::empty();
+        builder.add_tags(["b"]);
+        let mut v = vec![String::from("<b>bold</b> <i>italic</i>")];
+        sanitize_html_with(&mut v, &builder);
+        assert_eq!(v, ["<b>bold</b> italic"]);
+    }
+
+    #[cfg(all(feature = "sanitize", feature = "validify", feature = "json"))]
+    #[tokio::test]
+    async fn sanitize_before_validify() -> anyhow::Result<()> {
+        use crate::{ValidifiedByRef, VALIDATION_ERROR_STATUS};
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use reqwest::Client;
+        use serde::Deserialize;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+        use validify::Validify;
+
+        #[derive(Deserialize, Validify)]
+        struct Comment {
+            #[modify(custom(sanitize_html), trim)]
+            #[validate(length(min = 1, max = 10))]
+            body: String,
+        }
+
+        async fn handler(ValidifiedByRef(Json(comment)): ValidifiedByRef<Json<Comment>>) -> String {
+            comment.body
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        // Too long before sanitization
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "body": "<b>hi</b><script>alert(1)</script> " }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "<b>hi</b>");
+
+        // Empty after sanitization
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "body": "<script>alert(1)</script>" }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        Ok(())
+    }
+
+    #[cfg(feature = "query")]
+    #[tokio::test]
+    async fn normalized_query() {
+        use axum::extract::Query;
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize)]
+        struct Search {
+            q: String,
+            tags: Option<String>,
+        }
+
+        impl Normalize for Search {
+            fn normalize(&mut self) {
+                collapse_whitespace(&mut self.q);
+                lowercase(&mut self.q);
+                trim(&mut self.tags);
+            }
+        }
+
+        async fn extract(uri: &'static str) -> Option<Search> {
+            let (mut parts, _) = Request::builder()
+                .uri(uri)
+                .body(())
+                .expect("build request")
+                .into_parts();
+            Normalized::<Query<Search>>::from_request_parts(&mut parts, &())
+                .await
+                .ok()
+                .map(|Normalized(Query(search))| search)
+        }
+
+        let search = extract("/?q=%20Rust%20%20Axum%20&tags=%20web%20")
+            .await
+            .expect("valid query");
+        assert_eq!(search.q, "rust axum");
+        assert_eq!(search.tags.as_deref(), Some("web"));
+        assert!(extract("/?tags=web").await.is_none());
+    }
+
+    #[cfg(all(feature = "validator", feature = "json"))]
+    #[tokio::test]
+    async fn normalized_before_validation() -> anyhow::Result<()> {
+        use crate::{Valid, VALIDATION_ERROR_STATUS};
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use reqwest::Client;
+        use serde::Deserialize;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+        use validator::Validate;
+
+        #[derive(Deserialize, Validate)]
+        struct User {
+            #[validate(length(min = 1, max = 5))]
+            name: String,
+        }
+
+        impl Normalize for User {
+            fn normalize(&mut self) {
+                strip_control(&mut self.name);
+                trim(&mut self.name);
+            }
+        }
+
+        async fn handler(Valid(Normalized(Json(user))): Valid<Normalized<Json<User>>>) -> String {
+            user.name
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        // Too long before normalization
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "name": "  \u{0}abc\t  " }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "abc");
+
+        // Empty after normalization
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "name": "  \n " }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        Ok(())
+    }
+}
@@ -0,0 +1,790 @@
+//! # Support for request data composed of validated newtypes
+//!
+//! ## Feature
+//!
+//! Enable the `nutype` feature to use `Nutype<Json<T>>`, `Nutype<Query<T>>` and `Nutype<Form<T>>`
+//! (with the `json`, `query` and `form` features respectively).
+//!
+//! ## Usage
+//!
+//! 1. Define your domain values with [`nutype`](https://crates.io/crates/nutype) (or any newtype whose
+//!    `Deserialize` implementation validates the value), and derive `Deserialize` for your data type `T`
+//!    composed of them.
+//! 2. In your handler function, use `Nutype<Json<T>>`, `Nutype<Query<T>>` or `Nutype<Form<T>>` as some parameter's type.
+//!
+//! Newtypes are validated while they are deserialized, so a plain `Json<T>` would reject an invalid value with an
+//! opaque deserialization error. `Nutype<E>` reports it instead as [`FieldErrors`], carrying the path of each
+//! offending field and the newtype's error message, through the standard validation rejection.
+//! They are serialized in the same format as `validator::ValidationErrors`.
+//!
+//! Malformed requests (e.g. a missing content type or invalid JSON syntax) are still rejected by the inner extractor.
+//! Any value that can't be deserialized into its field, including values of the wrong type and missing fields,
+//! is reported as a field error. Every invalid value is reported, by deserializing each field on its own
+//! once the data is found invalid. Missing fields are only reported if every supplied value is valid.
+//!
+//! `Nutype<E>` can be wrapped by the other extractors, e.g. `Valid<Nutype<Json<T>>>`,
+//! to validate the deserialized data as well.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use axum::routing::post;
+//! use axum::{Json, Router};
+//! use axum_valid::nutype::Nutype;
+//! use nutype::nutype;
+//! use serde::Deserialize;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty, len_char_max = 32),
+//!     derive(Debug, Deserialize)
+//! )]
+//! pub struct Username(String);
+//!
+//! #[nutype(validate(greater_or_equal = 1, less_or_equal = 10), derive(Debug, Deserialize))]
+//! pub struct Priority(u8);
+//!
+//! #[derive(Debug, Deserialize)]
+//! pub struct Ticket {
+//!     pub username: Username,
+//!     pub priorities: Vec<Priority>,
+//! }
+//!
+//! // `{"username":"","priorities":[1, 11]}` is rejected with the field errors (with the `into_json` feature)
+//! // `{"priorities[1]":[{"code":"invalid","message":"...","params":{}}],"username":[{"code":"invalid",...}]}`.
+//! async fn handler(Nutype(Json(ticket)): Nutype<Json<Ticket>>) {
+//!     println!("{}", ticket.username.into_inner());
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use tokio::net::TcpListener;
+//! let router = Router::new().route("/tickets", post(handler));
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "validator")]
+use crate::HasValidateArgs;
+use crate::{HasValidate, ValidationRejection};
+#[cfg(any(feature = "json", feature = "query", feature = "form"))]
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// # `Nutype` data extractor
+///
+/// Nutype deserializes data composed of validated newtypes, reporting invalid values as field errors,
+/// see the [module documentation](self).
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Nutype<E>(pub E);
+
+impl<E> Deref for Nutype<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for Nutype<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display> Display for Nutype<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E> Nutype<E> {
+    /// Consumes the `Nutype` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T> aide::OperationInput for Nutype<T>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+impl<E: HasValidate> HasValidate for Nutype<E> {
+    type Validate = E::Validate;
+    fn get_validate(&self) -> &Self::Validate {
+        self.0.get_validate()
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<'v, E: HasValidateArgs<'v>> HasValidateArgs<'v> for Nutype<E> {
+    type ValidateArgs = E::ValidateArgs;
+    fn get_validate_args(&self) -> &Self::ValidateArgs {
+        self.0.get_validate_args()
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<E: crate::HasModify> crate::HasModify for Nutype<E> {
+    type Modify = E::Modify;
+    fn get_modify(&mut self) -> &mut Self::Modify {
+        self.0.get_modify()
+    }
+}
+
+/// A value that could not be deserialized into its field.
+///
+/// Serialized like a `validator::ValidationError`, e.g. `{"code":"invalid","message":"Priority is too big.","params":{}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Path of the field, such as `user.name` or `items[1]`, `.` for the data itself.
+    pub path: String,
+    /// Error code, [`FieldError::REQUIRED`] for missing fields, [`FieldError::INVALID`] otherwise.
+    pub code: &'static str,
+    /// Description of the error, the newtype's error message for invalid values.
+    pub message: String,
+}
+
+impl FieldError {
+    /// Code of a value that can't be deserialized into its field
+    pub const INVALID: &'static str = "invalid";
+    /// Code of a missing field
+    pub const REQUIRED: &'static str = "required";
+
+    /// Creates a field error from the path and the error of a deserialization, whose first segments
+    /// are replaced by the formatted segments of `prefix`.
+    fn with_prefix<E: Display>(
+        prefix: &[String],
+        path: &serde_path_to_error::Path,
+        error: &E,
+    ) -> Self {
+        let mut formatted = String::new();
+        for (i, segment) in path.iter().enumerate() {
+            let step = match prefix.get(i) {
+                Some(step) => step.clone(),
+                None => segment.to_string(),
+            };
+            if !formatted.is_empty() && !step.starts_with('[') {
+                formatted.push('.');
+            }
+            formatted.push_str(&step);
+        }
+        let message = error.to_string();
+        let missing = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.strip_suffix('`'));
+        if let Some(field) = missing {
+            if !formatted.is_empty() {
+                formatted.push('.');
+            }
+            formatted.push_str(field);
+        }
+        if formatted.is_empty() {
+            formatted.push('.');
+        }
+        Self {
+            path: formatted,
+            code: if missing.is_some() {
+                Self::REQUIRED
+            } else {
+                Self::INVALID
+            },
+            message,
+        }
+    }
+}
+
+impl<E: std::error::Error> From<serde_path_to_error::Error<E>> for FieldError {
+    fn from(error: serde_path_to_error::Error<E>) -> Self {
+        Self::with_prefix(&[], error.path(), error.inner())
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+impl Serialize for FieldError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("code", self.code)?;
+        map.serialize_entry("message", &self.message)?;
+        map.serialize_entry("params", &BTreeMap::<&str, ()>::new())?;
+        map.end()
+    }
+}
+
+/// Values that could not be deserialized into their fields.
+///
+/// Serialized like `validator::ValidationErrors`, as a map from the field paths to their errors,
+/// e.g. `{"items[1]":[{"code":"invalid","message":"Priority is too big.","params":{}}]}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldErrors(pub Vec<FieldError>);
+
+impl Deref for FieldErrors {
+    type Target = Vec<FieldError>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for FieldErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FieldErrors {}
+
+impl Serialize for FieldErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut fields = BTreeMap::<&str, Vec<&FieldError>>::new();
+        for error in &self.0 {
+            fields.entry(&error.path).or_default().push(error);
+        }
+        fields.serialize(serializer)
+    }
+}
+
+/// `NutypeRejection` is returned when the `Nutype` extractor fails.
+///
+pub type NutypeRejection<E> = ValidationRejection<FieldErrors, E>;
+
+impl<E> From<FieldErrors> for NutypeRejection<E> {
+    fn from(value: FieldErrors) -> Self {
+        Self::Valid(value)
+    }
+}
+
+/// A step of the path to a probed value, with the index of array elements in the original data.
+#[cfg(feature = "json")]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+#[cfg(feature = "json")]
+impl Step {
+    /// Returns `true` if `segment` is this step in a probe, where each array holds a single element.
+    fn probes(&self, segment: &serde_path_to_error::Segment) -> bool {
+        use serde_path_to_error::Segment;
+
+        match (self, segment) {
+            (Step::Key(key), Segment::Map { key: other } | Segment::Enum { variant: other }) => {
+                key == other
+            }
+            (Step::Index(_), Segment::Seq { index }) => *index == 0,
+            _ => false,
+        }
+    }
+
+    /// Formats this step as a segment of a field path.
+    fn segment(&self) -> String {
+        match self {
+            Step::Key(key) => key.clone(),
+            Step::Index(index) => format!("[{index}]"),
+        }
+    }
+}
+
+/// Deserializes `T` from a JSON value, reporting every value that can't be deserialized into its field.
+#[cfg(feature = "json")]
+fn from_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, FieldErrors> {
+    if let Ok(data) = serde_path_to_error::deserialize(&value) {
+        return Ok(data);
+    }
+    let mut errors = FieldErrors::default();
+    probe::<T>(&value, &|value| value, &mut Vec::new(), &mut errors);
+    Err(errors)
+}
+
+/// Deserializes `T` from the probe `wrap(value.clone())`, in which `value` is the only value of its
+/// ancestors, and records the errors of `value`.
+///
+/// Returns `false` if `value` is valid, that is the probe fails at none of its fields.
+/// Each field is probed in turn, and `value` itself is reported if none of them fails.
+#[cfg(feature = "json")]
+fn probe<T: DeserializeOwned>(
+    value: &serde_json::Value,
+    wrap: &dyn Fn(serde_json::Value) -> serde_json::Value,
+    steps: &mut Vec<Step>,
+    errors: &mut FieldErrors,
+) -> bool {
+    use serde_json::Value;
+
+    let Err(error) = serde_path_to_error::deserialize::<_, T>(wrap(value.clone())) else {
+        return false;
+    };
+    let within = error.path().iter().count() >= steps.len()
+        && steps
+            .iter()
+            .zip(error.path().iter())
+            .all(|(step, segment)| step.probes(segment));
+    if !within {
+        return false;
+    }
+    let mut found = false;
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let wrap_child = |child| {
+                    wrap(Value::Object(serde_json::Map::from_iter([(
+                        key.clone(),
+                        child,
+                    )])))
+                };
+                steps.push(Step::Key(key.clone()));
+                found |= probe::<T>(child, &wrap_child, steps, errors);
+                steps.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                let wrap_child = |child| wrap(Value::Array(vec![child]));
+                steps.push(Step::Index(index));
+                found |= probe::<T>(child, &wrap_child, steps, errors);
+                steps.pop();
+            }
+        }
+        _ => {}
+    }
+    if !found {
+        let prefix: Vec<_> = steps.iter().map(Step::segment).collect();
+        errors.0.push(FieldError::with_prefix(
+            &prefix,
+            error.path(),
+            error.inner(),
+        ));
+    }
+    true
+}
+
+/// Deserializes `T` from `key=value` pairs, as `Query` and `Form` do,
+/// reporting every value that can't be deserialized into its field.
+#[cfg(any(feature = "query", feature = "form"))]
+fn from_pairs<T: DeserializeOwned>(pairs: Vec<(String, String)>) -> Result<T, FieldErrors> {
+    let error = match serde_path_to_error::deserialize(crate::pairs::deserializer(pairs.clone())) {
+        Ok(data) => return Ok(data),
+        Err(error) => error,
+    };
+    let mut errors = FieldErrors::default();
+    for (key, value) in pairs {
+        let single = crate::pairs::deserializer([(key.clone(), value)]);
+        if let Err(error) = serde_path_to_error::deserialize::<_, T>(single) {
+            if matches!(
+                error.path().iter().next(),
+                Some(serde_path_to_error::Segment::Map { key: field }) if *field == key
+            ) {
+                errors.0.push(FieldError::from(error));
+            }
+        }
+    }
+    if errors.is_empty() {
+        errors.0.push(FieldError::from(error));
+    }
+    Err(errors)
+}
+
+#[cfg(feature = "json")]
+impl<State, T> axum::extract::FromRequest<State> for Nutype<axum::Json<T>>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = NutypeRejection<axum::extract::rejection::JsonRejection>;
+
+    async fn from_request(
+        req: axum::extract::Request,
+        state: &State,
+    ) -> Result<Self, Self::Rejection> {
+        let axum::Json(value) = axum::Json::<serde_json::Value>::from_request(req, state)
+            .await
+            .map_err(NutypeRejection::Inner)?;
+        Ok(Nutype(axum::Json(from_value(value)?)))
+    }
+}
+
+#[cfg(feature = "query")]
+impl<State, T> axum::extract::FromRequestParts<State> for Nutype<axum::extract::Query<T>>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = NutypeRejection<axum::extract::rejection::QueryRejection>;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &State,
+    ) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(pairs) =
+            axum::extract::Query::<Vec<(String, String)>>::from_request_parts(parts, state)
+                .await
+                .map_err(NutypeRejection::Inner)?;
+        Ok(Nutype(axum::extract::Query(from_pairs(pairs)?)))
+    }
+}
+
+#[cfg(feature = "form")]
+impl<State, T> axum::extract::FromRequest<State> for Nutype<axum::Form<T>>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = NutypeRejection<axum::extract::rejection::FormRejection>;
+
+    async fn from_request(
+        req: axum::extract::Request,
+        state: &State,
+    ) -> Result<Self, Self::Rejection> {
+        let axum::Form(pairs) = axum::Form::<Vec<(String, String)>>::from_request(req, state)
+            .await
+            .map_err(NutypeRejection::Inner)?;
+        Ok(Nutype(axum::Form(from_pairs(pairs)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::nutype::nutype;
+    use serde::Deserialize;
+    use std::error::Error;
+    use std::io;
+
+    const NUTYPE: &str = "nutype";
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 8),
+        derive(Debug, Clone, PartialEq, Serialize, Deserialize, AsRef)
+    )]
+    pub struct Username(String);
+
+    #[nutype(
+        validate(greater_or_equal = 1, less_or_equal = 10),
+        derive(Debug, Clone, Copy, PartialEq, Deserialize)
+    )]
+    pub struct Priority(u8);
+
+    #[derive(Debug, Deserialize)]
+    struct Ticket {
+        username: Username,
+        priority: Priority,
+    }
+
+    #[test]
+    fn nutype_deref_deref_mut_into_inner() {
+        let mut inner = String::from(NUTYPE);
+        let mut v = Nutype(inner.clone());
+        assert_eq!(&inner, v.deref());
+        inner.push_str(NUTYPE);
+        v.deref_mut().push_str(NUTYPE);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[test]
+    fn serialize_field_errors() -> anyhow::Result<()> {
+        let errors = FieldErrors(vec![
+            FieldError {
+                path: String::from("items[1]"),
+                code: FieldError::INVALID,
+                message: String::from(NUTYPE),
+            },
+            FieldError {
+                path: String::from("name"),
+                code: FieldError::REQUIRED,
+                message: String::from("missing field `name`"),
+            },
+        ]);
+        assert_eq!(
+            errors.to_string(),
+            "items[1]: nutype\nname: missing field `name`"
+        );
+        assert_eq!(
+            serde_json::to_value(&errors)?,
+            serde_json::json!({
+                "items[1]": [{ "code": "invalid", "message": NUTYPE, "params": {} }],
+                "name": [{ "code": "required", "message": "missing field `name`", "params": {} }],
+            })
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn from_value_collects_errors() {
+        #[derive(Debug, Deserialize)]
+        struct Tickets {
+            #[allow(dead_code)]
+            tickets: Vec<Ticket>,
+            #[allow(dead_code)]
+            owner: Username,
+        }
+
+        let errors = from_value::<Tickets>(serde_json::json!({
+            "tickets": [
+                { "username": "", "priority": 1 },
+                { "username": "admin", "priority": 1 },
+                { "username": "admin", "priority": 20 },
+            ],
+            "owner": "administrator",
+        }))
+        .expect_err("invalid tickets");
+        let mut paths: Vec<_> = errors.iter().map(|error| error.path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            ["owner", "tickets[0].username", "tickets[2].priority"]
+        );
+        assert!(errors.iter().all(|error| error.code == FieldError::INVALID));
+
+        let errors = from_value::<Tickets>(serde_json::json!({
+            "tickets": [{ "username": "admin" }],
+            "owner": "admin",
+        }))
+        .expect_err("missing priority");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "tickets[0].priority");
+        assert_eq!(errors[0].code, FieldError::REQUIRED);
+
+        let errors = from_value::<Tickets>(serde_json::json!([])).expect_err("not an object");
+        assert_eq!(errors[0].path, ".");
+    }
+
+    #[cfg(feature = "query")]
+    #[test]
+    fn pairs() {
+        fn pairs(username: &str, priority: &str) -> Vec<(String, String)> {
+            vec![
+                (String::from("username"), String::from(username)),
+                (String::from("priority"), String::from(priority)),
+            ]
+        }
+
+        let ticket: Ticket = from_pairs(pairs(" admin ", "3")).expect("valid ticket");
+        assert_eq!(ticket.username.as_ref(), "admin");
+        assert_eq!(ticket.priority.into_inner(), 3);
+
+        let errors = from_pairs::<Ticket>(pairs("administrator", "3")).expect_err("long name");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "username");
+        let errors = from_pairs::<Ticket>(pairs("admin", "11")).expect_err("big priority");
+        assert_eq!(errors[0].path, "priority");
+        let errors = from_pairs::<Ticket>(pairs("admin", "high")).expect_err("not a number");
+        assert_eq!(errors[0].path, "priority");
+        let errors = from_pairs::<Ticket>(pairs("", "0")).expect_err("both invalid");
+        let paths: Vec<_> = errors.iter().map(|error| error.path.as_str()).collect();
+        assert_eq!(paths, ["username", "priority"]);
+
+        let errors = from_pairs::<Ticket>(vec![(String::from("username"), String::from("a"))])
+            .expect_err("missing priority");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "priority");
+        assert_eq!(errors[0].code, FieldError::REQUIRED);
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn nutype_json() -> anyhow::Result<()> {
+        use crate::VALIDATION_ERROR_STATUS;
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use reqwest::Client;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+
+        #[derive(Debug, Deserialize)]
+        struct Tickets {
+            tickets: Vec<Ticket>,
+        }
+
+        async fn handler(Nutype(Json(data)): Nutype<Json<Tickets>>) -> String {
+            data.tickets[0].username.as_ref().to_owned()
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "tickets": [{ "username": "admin", "priority": 1 }] }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "admin");
+
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "tickets": [
+                { "username": "admin", "priority": 1 },
+                { "username": "admin", "priority": 20 }
+            ] }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        #[cfg(feature = "into_json")]
+        assert_eq!(
+            response.json::<serde_json::Value>().await?["tickets[1].priority"][0]["code"],
+            FieldError::INVALID
+        );
+        #[cfg(not(feature = "into_json"))]
+        assert!(response.text().await?.starts_with("tickets[1].priority: "));
+
+        let response = client
+            .post(&url)
+            .header("content-type", "application/json")
+            .body("{")
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[cfg(feature = "form")]
+    #[tokio::test]
+    async fn nutype_form() {
+        use axum::body::Body;
+        use axum::extract::{FromRequest, Request};
+        use axum::http::header::CONTENT_TYPE;
+        use axum::Form;
+
+        fn request(body: &'static str) -> Request {
+            Request::builder()
+                .method("POST")
+                .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(body))
+                .expect("build request")
+        }
+
+        let Nutype(Form(ticket)) =
+            Nutype::<Form<Ticket>>::from_request(request("username=admin&priority=5"), &())
+                .await
+                .expect("valid form");
+        assert_eq!(ticket.priority.into_inner(), 5);
+        assert!(matches!(
+            Nutype::<Form<Ticket>>::from_request(request("username=&priority=5"), &()).await,
+            Err(NutypeRejection::Valid(errors)) if errors[0].path == "username"
+        ));
+        assert!(matches!(
+            Nutype::<Form<Ticket>>::from_request(
+                Request::builder()
+                    .method("POST")
+                    .body(Body::from("username=admin&priority=5"))
+                    .expect("build request"),
+                &()
+            )
+            .await,
+            Err(NutypeRejection::Inner(_))
+        ));
+    }
+
+    #[test]
+    fn display_error() {
+        fn error() -> FieldErrors {
+            FieldErrors(vec![FieldError {
+                path: String::from("username"),
+                code: FieldError::INVALID,
+                message: String::from(NUTYPE),
+            }])
+        }
+
+        // NutypeRejection::Valid Display
+        let s = error().to_string();
+        let vr = NutypeRejection::<String>::Valid(error());
+        assert_eq!(vr.to_string(), s);
+
+        // NutypeRejection::Inner Display
+        let inner = String::from(NUTYPE);
+        let vr = NutypeRejection::<String>::Inner(inner.clone());
+        assert_eq!(inner.to_string(), vr.to_string());
+
+        // NutypeRejection::Valid Error
+        let vr = NutypeRejection::<io::Error>::Valid(error());
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<FieldErrors>().is_some())
+        );
+
+        // NutypeRejection::Inner Error
+        let vr = NutypeRejection::<io::Error>::Inner(io::Error::other(NUTYPE));
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<io::Error>().is_some())
+        );
+    }
+
+    #[cfg(all(feature = "json", feature = "validator"))]
+    #[tokio::test]
+    async fn valid_nutype() {
+        use crate::{Valid, ValidRejection};
+        use axum::body::Body;
+        use axum::extract::{FromRequest, Request};
+        use axum::http::header::CONTENT_TYPE;
+        use axum::Json;
+        use validator::Validate;
+
+        #[derive(Debug, Deserialize, Validate)]
+        struct Team {
+            #[validate(length(min = 2))]
+            members: Vec<Username>,
+        }
+
+        fn request(body: &'static str) -> Request {
+            Request::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .expect("build request")
+        }
+
+        let Valid(Nutype(Json(team))) =
+            Valid::<Nutype<Json<Team>>>::from_request(request(r#"{"members":["a","b"]}"#), &())
+                .await
+                .expect("valid team");
+        assert_eq!(team.members.len(), 2);
+        assert!(matches!(
+            Valid::<Nutype<Json<Team>>>::from_request(request(r#"{"members":["a"]}"#), &()).await,
+            Err(ValidRejection::Valid(errors)) if errors.field_errors().contains_key("members")
+        ));
+        assert!(matches!(
+            Valid::<Nutype<Json<Team>>>::from_request(request(r#"{"members":["a",""]}"#), &())
+                .await,
+            Err(ValidRejection::Inner(NutypeRejection::Valid(errors))) if errors[0].path == "members[1]"
+        ));
+    }
+}
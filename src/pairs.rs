@@ -0,0 +1,247 @@
+//! Deserialization of name-value pairs, such as headers, cookies and decoded query strings.
+//!
+//! Values are deserialized like query string values, so numbers, booleans,
+//! optional fields and unit enum variants are parsed from their text.
+
+use serde::de::value::{Error, MapDeserializer};
+#[cfg(any(feature = "headers", feature = "extra_cookie"))]
+use serde::de::DeserializeOwned;
+use serde::de::{Error as _, IntoDeserializer, Unexpected, Visitor};
+use serde::Deserializer;
+
+/// Deserializes `T` from name-value pairs, each name being a field of `T`.
+#[cfg(feature = "extra_cookie")]
+pub(crate) fn from_pairs<T: DeserializeOwned>(
+    pairs: impl IntoIterator<Item = (String, String)>,
+) -> Result<T, Error> {
+    T::deserialize(deserializer(pairs))
+}
+
+/// Deserializes `T` from name-value pairs whose values may have failed to decode.
+///
+/// The error of a value is only returned if the value is deserialized, so values that `T` ignores can't fail.
+#[cfg(feature = "headers")]
+pub(crate) fn from_fallible_pairs<T: DeserializeOwned>(
+    pairs: impl IntoIterator<Item = (String, Result<String, Error>)>,
+) -> Result<T, Error> {
+    T::deserialize(fallible_deserializer(pairs))
+}
+
+/// Returns a deserializer of name-value pairs, each name being a field.
+#[cfg(any(feature = "extra_cookie", feature = "nutype"))]
+pub(crate) fn deserializer(
+    pairs: impl IntoIterator<Item = (String, String)>,
+) -> impl Deserializer<'static, Error = Error> {
+    fallible_deserializer(pairs.into_iter().map(|(name, value)| (name, Ok(value))))
+}
+
+/// Returns a deserializer of name-value pairs, each name being a field, whose values may have failed to decode.
+fn fallible_deserializer(
+    pairs: impl IntoIterator<Item = (String, Result<String, Error>)>,
+) -> impl Deserializer<'static, Error = Error> {
+    MapDeserializer::new(
+        pairs
+            .into_iter()
+            .map(|(name, value)| (Part(Ok(name)), Part(value))),
+    )
+}
+
+/// Defines a rejection wrapping the error of deserializing pairs, responded with `400 Bad Request`.
+#[cfg(any(feature = "headers", feature = "extra_cookie"))]
+macro_rules! pairs_rejection {
+    ($(#[$meta:meta])* $name:ident, $what:literal) => {
+        $(#[$meta])*
+        ///
+        /// Responds with `400 Bad Request`.
+        #[derive(Debug)]
+        pub struct $name(pub serde::de::value::Error);
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, concat!("Failed to deserialize ", $what, ": {}"), self.0)
+            }
+        }
+
+        impl std::error::Error for $name {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        impl axum::response::IntoResponse for $name {
+            fn into_response(self) -> axum::response::Response {
+                (axum::http::StatusCode::BAD_REQUEST, self.to_string()).into_response()
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "headers", feature = "extra_cookie"))]
+pub(crate) use pairs_rejection;
+
+/// A name or a value of a pair, or the error to return if a value that failed to decode is deserialized.
+struct Part(Result<String, Error>);
+
+impl IntoDeserializer<'_, Error> for Part {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                let text = self.0?;
+                match text.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(Error::invalid_value(Unexpected::Str(&text), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Part {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0?)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self.0?.into_deserializer())
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier
+    }
+}
+
+#[cfg(all(test, any(feature = "headers", feature = "extra_cookie")))]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Theme {
+        Dark,
+        Light,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Data {
+        name: String,
+        size: Option<u32>,
+        enabled: bool,
+        theme: Theme,
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn deserialize(pairs: Vec<(String, String)>) -> Result<Data, Error> {
+        Data::deserialize(fallible_deserializer(
+            pairs.into_iter().map(|(name, value)| (name, Ok(value))),
+        ))
+    }
+
+    #[test]
+    fn from_pairs_keeps_values_verbatim() -> anyhow::Result<()> {
+        let data = deserialize(pairs(&[
+            ("name", "a+b%20c=d&e"),
+            ("size", "3"),
+            ("enabled", "true"),
+            ("theme", "dark"),
+            ("other", "ignored"),
+        ]))?;
+        assert_eq!(
+            data,
+            Data {
+                name: String::from("a+b%20c=d&e"),
+                size: Some(3),
+                enabled: true,
+                theme: Theme::Dark,
+            }
+        );
+
+        let error = deserialize(pairs(&[
+            ("name", "a"),
+            ("size", "large"),
+            ("enabled", "true"),
+            ("theme", "light"),
+        ]))
+        .expect_err("size is not a number");
+        assert!(error.to_string().contains("large"));
+        assert!(deserialize(pairs(&[("name", "a"), ("enabled", "true")])).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "headers")]
+    #[test]
+    fn from_fallible_pairs_fails_on_read_values() {
+        let fallible = |value: Result<&str, &str>| {
+            vec![
+                (String::from("name"), Ok(String::from("a"))),
+                (String::from("enabled"), Ok(String::from("true"))),
+                (String::from("theme"), Ok(String::from("dark"))),
+                (
+                    String::from("size"),
+                    value.map(str::to_owned).map_err(Error::custom),
+                ),
+                (String::from("other"), Err(Error::custom("undecodable"))),
+            ]
+        };
+        let data = from_fallible_pairs::<Data>(fallible(Ok("3"))).expect("other is ignored");
+        assert_eq!(data.size, Some(3));
+        let error = from_fallible_pairs::<Data>(fallible(Err("undecodable size")))
+            .expect_err("size is read");
+        assert_eq!(error.to_string(), "undecodable size");
+    }
+}
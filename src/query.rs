@@ -9,6 +9,8 @@
 //! 1. Implement `Deserialize` and `Validate` for your data type `T`.
 //! 2. In your handler function, use `Valid<Query<T>>` as some parameter's type.
 //!
+//! Enable the `nested_query` feature to support nested and repeated parameters, see [`nested`].
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -85,6 +87,9 @@
 //! # }
 //! ```
 
+#[cfg(feature = "nested_query")]
+pub mod nested;
+
 use crate::HasValidate;
 #[cfg(feature = "validator")]
 use crate::HasValidateArgs;
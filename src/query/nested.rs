@@ -0,0 +1,586 @@
+//! # Support for nested and repeated query parameters
+//!
+//! ## Feature
+//!
+//! Enable the `nested_query` feature to use `Valid<NestedQuery<T>>`.
+//!
+//! ## Usage
+//!
+//! 1. Implement `Deserialize` and `Validate` for your data type `T`.
+//! 2. In your handler function, use `Valid<NestedQuery<T>>` as some parameter's type.
+//!
+//! `Query` from `axum` only supports flat `key=value` pairs, and `Query` from `axum-extra` adds repeated keys.
+//! `NestedQuery<T>` deserializes the query string with [`serde_qs`](https://docs.rs/serde_qs), supporting both
+//! repeated keys (`tag=a&tag=b`) and nested structures with brackets (`filter[name]=x&filter[tags][0]=a`),
+//! percent-encoded or not. A scalar field given several values takes the last one, and nesting is limited to
+//! 5 levels.
+//!
+//! When a value can't be deserialized or a required field is missing, [`NestedQueryRejection`] reports the query key
+//! of the offending value or of the missing field, such as `filter[tags][1]`, with `400 Bad Request`.
+//!
+//! Only deserialization errors use bracketed query keys. Validation errors are reported by the backend as usual,
+//! with the backend's paths, e.g. `{"filter":{"name":[...]}}` for `filter[name]` with `validator` and `into_json`,
+//! or `filter.name` with `garde`.
+//!
+//! `NestedQuery<T>` works with `Valid`, `ValidEx`, `Garde`, and the `validify` extractors.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::routing::get;
+//!     use axum::Router;
+//!     use axum_valid::query::nested::NestedQuery;
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/query", get(handler))
+//!     }
+//!
+//!     // GET /query?tag=a&tag=b&filter[name]=x&filter[min_age]=18
+//!     async fn handler(Valid(NestedQuery(parameter)): Valid<NestedQuery<Parameter>>) {
+//!         assert!(parameter.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[validate(length(max = 5))]
+//!         #[serde(default)]
+//!         pub tag: Vec<String>,
+//!         #[validate(nested)]
+//!         pub filter: Filter,
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Filter {
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub name: String,
+//!         #[validate(range(min = 0, max = 150))]
+//!         pub min_age: u8,
+//!     }
+//! }
+//!
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::routing::get;
+//!     use axum::Router;
+//!     use axum_valid::query::nested::NestedQuery;
+//!     use axum_valid::Garde;
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/query", get(handler))
+//!     }
+//!
+//!     // GET /query?tag=a&tag=b&filter[name]=x&filter[min_age]=18
+//!     async fn handler(Garde(NestedQuery(parameter)): Garde<NestedQuery<Parameter>>) {
+//!         assert!(parameter.validate_with(&()).is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[garde(length(max = 5))]
+//!         #[serde(default)]
+//!         pub tag: Vec<String>,
+//!         #[garde(dive)]
+//!         pub filter: Filter,
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Filter {
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub name: String,
+//!         #[garde(range(min = 0, max = 150))]
+//!         pub min_age: u8,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::HasValidate;
+#[cfg(feature = "validator")]
+use crate::HasValidateArgs;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+use std::fmt::{Display, Formatter, Write};
+use std::ops::{Deref, DerefMut};
+#[cfg(feature = "validator")]
+use validator::ValidateArgs;
+
+/// # `NestedQuery` extractor
+///
+/// `NestedQuery` deserializes the query string with nested and repeated parameters,
+/// see the [module documentation](self).
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NestedQuery<T>(pub T);
+
+impl<T> Deref for NestedQuery<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for NestedQuery<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display> Display for NestedQuery<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> NestedQuery<T> {
+    /// Consumes the `NestedQuery` and returns the data within.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T> aide::OperationInput for NestedQuery<T>
+where
+    axum::extract::Query<T>: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        axum::extract::Query::<T>::operation_input(ctx, operation);
+    }
+}
+
+/// `NestedQueryRejection` is returned when the `NestedQuery` extractor fails to deserialize the query string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedQueryRejection {
+    /// Query key of the offending value or of the missing field, such as `filter[tags][1]`,
+    /// empty if the query string itself is invalid.
+    pub key: String,
+    /// Description of the error
+    pub message: String,
+}
+
+impl Display for NestedQueryRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.key.is_empty() {
+            write!(f, "Failed to deserialize query string: {}", self.message)
+        } else {
+            write!(
+                f,
+                "Failed to deserialize query string: {}: {}",
+                self.key, self.message
+            )
+        }
+    }
+}
+
+impl std::error::Error for NestedQueryRejection {}
+
+impl IntoResponse for NestedQueryRejection {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
+
+/// Writes the path of a deserialization error as a query key, e.g. `filter[tags][1]`,
+/// followed by the missing field if `message` reports one.
+fn query_key(path: &serde_path_to_error::Path, message: &str) -> String {
+    use serde_path_to_error::Segment;
+    let mut key = String::new();
+    for segment in path.iter() {
+        let name = match segment {
+            Segment::Seq { index } => {
+                let _ = write!(key, "[{index}]");
+                continue;
+            }
+            Segment::Map { key } => key.as_str(),
+            Segment::Enum { variant } => variant.as_str(),
+            Segment::Unknown => "?",
+        };
+        if key.is_empty() {
+            key.push_str(name);
+        } else {
+            let _ = write!(key, "[{name}]");
+        }
+    }
+    let missing = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.strip_suffix('`'));
+    if let Some(field) = missing {
+        if key.is_empty() {
+            key.push_str(field);
+        } else {
+            let _ = write!(key, "[{field}]");
+        }
+    }
+    key
+}
+
+/// Deserializes `T` from a query string with nested and repeated parameters.
+fn from_query<T: DeserializeOwned>(query: &str) -> Result<T, NestedQueryRejection> {
+    let config = serde_qs::Config::new().use_form_encoding(true);
+    let deserializer =
+        serde_qs::Deserializer::with_config(config, query.as_bytes()).map_err(|error| {
+            NestedQueryRejection {
+                key: String::new(),
+                message: error.to_string(),
+            }
+        })?;
+    serde_path_to_error::deserialize(deserializer).map_err(|error| {
+        let message = error.inner().to_string();
+        NestedQueryRejection {
+            key: query_key(error.path(), &message),
+            message,
+        }
+    })
+}
+
+impl<State, T> FromRequestParts<State> for NestedQuery<T>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = NestedQueryRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _: &State) -> Result<Self, Self::Rejection> {
+        from_query(parts.uri.query().unwrap_or_default()).map(NestedQuery)
+    }
+}
+
+impl<T> HasValidate for NestedQuery<T> {
+    type Validate = T;
+    fn get_validate(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<'v, T: ValidateArgs<'v>> HasValidateArgs<'v> for NestedQuery<T> {
+    type ValidateArgs = T;
+    fn get_validate_args(&self) -> &Self::ValidateArgs {
+        &self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Modify> crate::HasModify for NestedQuery<T> {
+    type Modify = T;
+
+    fn get_modify(&mut self) -> &mut Self::Modify {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T> crate::PayloadExtractor for NestedQuery<T> {
+    type Payload = T;
+
+    fn get_payload(self) -> Self::Payload {
+        self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Validify + validify::ValidifyPayload> crate::HasValidify for NestedQuery<T> {
+    type Validify = T;
+    type PayloadExtractor = NestedQuery<T::Payload>;
+
+    fn from_validify(v: Self::Validify) -> Self {
+        NestedQuery(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    const NESTED_QUERY: &str = "nested_query";
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Filter {
+        name: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Parameter {
+        #[serde(default)]
+        tag: Vec<String>,
+        filter: Filter,
+        page: Option<u32>,
+    }
+
+    #[test]
+    fn nested_query_deref_deref_mut_into_inner() {
+        let mut inner = String::from(NESTED_QUERY);
+        let mut v = NestedQuery(inner.clone());
+        assert_eq!(&inner, v.deref());
+        inner.push_str(NESTED_QUERY);
+        v.deref_mut().push_str(NESTED_QUERY);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[test]
+    fn nested_and_repeated() {
+        let parameter: Parameter =
+            from_query("tag=a&tag=b&filter[name]=x&filter%5Btags%5D%5B0%5D=c&filter[tags][1]=d")
+                .expect("valid query");
+        assert_eq!(
+            parameter,
+            Parameter {
+                tag: vec![String::from("a"), String::from("b")],
+                filter: Filter {
+                    name: String::from("x"),
+                    tags: vec![String::from("c"), String::from("d")],
+                },
+                page: None,
+            }
+        );
+    }
+
+    #[test]
+    fn rejection_keys() {
+        let rejection =
+            from_query::<Parameter>("filter[name]=x&page=first").expect_err("invalid page");
+        assert_eq!(rejection.key, "page");
+
+        let rejection = from_query::<Parameter>("tag=a").expect_err("missing filter");
+        assert_eq!(rejection.key, "filter");
+
+        let rejection = from_query::<Parameter>("filter[tags][0]=a").expect_err("missing name");
+        assert_eq!(rejection.key, "filter[name]");
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Numbers {
+            filter: NumberFilter,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct NumberFilter {
+            ids: Vec<u32>,
+        }
+
+        let rejection =
+            from_query::<Numbers>("filter[ids][0]=1&filter[ids][1]=x").expect_err("invalid id");
+        assert_eq!(rejection.key, "filter[ids][1]");
+        assert!(rejection
+            .to_string()
+            .starts_with("Failed to deserialize query string: filter[ids][1]: "));
+    }
+
+    #[cfg(any(feature = "validator", feature = "garde", feature = "validify"))]
+    async fn check(router: axum::Router) -> anyhow::Result<()> {
+        use crate::VALIDATION_ERROR_STATUS;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(format!(
+                "{url}/?tag=a&tag=b&filter[name]=x&filter[tags][0]=c"
+            ))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = client
+            .get(format!("{url}/?tag=a&tag=b&tag=c&filter[name]=x"))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+
+        let response = client
+            .get(format!("{url}/?filter[name]=&filter[tags][0]=c"))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+
+        let response = client
+            .get(format!("{url}/?filter[name]=x&page=first"))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response
+            .text()
+            .await?
+            .starts_with("Failed to deserialize query string: page: "));
+        Ok(())
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn nested_query_validator() -> anyhow::Result<()> {
+        use crate::{Valid, ValidEx};
+        use axum::routing::get;
+        use axum::Router;
+        use validator::Validate;
+
+        #[derive(Deserialize, Validate)]
+        #[validate(context = usize)]
+        struct ParameterEx {
+            #[validate(custom(function = "max_tags", use_context))]
+            #[serde(default)]
+            tag: Vec<String>,
+            #[validate(nested)]
+            filter: ValidFilter,
+            #[allow(dead_code)]
+            page: Option<u32>,
+        }
+
+        #[derive(Deserialize, Validate)]
+        struct ValidParameter {
+            #[validate(length(max = 2))]
+            #[serde(default)]
+            tag: Vec<String>,
+            #[validate(nested)]
+            filter: ValidFilter,
+            #[allow(dead_code)]
+            page: Option<u32>,
+        }
+
+        #[derive(Deserialize, Validate)]
+        struct ValidFilter {
+            #[validate(length(min = 1))]
+            name: String,
+            #[serde(default)]
+            #[allow(dead_code)]
+            tags: Vec<String>,
+        }
+
+        fn max_tags(tag: &[String], max: &usize) -> Result<(), validator::ValidationError> {
+            (tag.len() <= *max)
+                .then_some(())
+                .ok_or_else(|| validator::ValidationError::new("max_tags"))
+        }
+
+        async fn valid(Valid(NestedQuery(p)): Valid<NestedQuery<ValidParameter>>) -> String {
+            p.tag.join(",")
+        }
+
+        async fn valid_ex(ValidEx(NestedQuery(p)): ValidEx<NestedQuery<ParameterEx>>) -> String {
+            p.tag.join(",")
+        }
+
+        check(Router::new().route("/", get(valid))).await?;
+        check(Router::new().route("/", get(valid_ex)).with_state(2usize)).await
+    }
+
+    #[cfg(feature = "garde")]
+    #[tokio::test]
+    async fn nested_query_garde() -> anyhow::Result<()> {
+        use crate::Garde;
+        use axum::routing::get;
+        use axum::Router;
+        use garde::Validate;
+
+        #[derive(Deserialize, Validate)]
+        struct GardeParameter {
+            #[garde(length(max = 2))]
+            #[serde(default)]
+            tag: Vec<String>,
+            #[garde(dive)]
+            filter: GardeFilter,
+            #[garde(skip)]
+            #[allow(dead_code)]
+            page: Option<u32>,
+        }
+
+        #[derive(Deserialize, Validate)]
+        struct GardeFilter {
+            #[garde(length(min = 1))]
+            name: String,
+            #[garde(skip)]
+            #[serde(default)]
+            #[allow(dead_code)]
+            tags: Vec<String>,
+        }
+
+        async fn garde(Garde(NestedQuery(p)): Garde<NestedQuery<GardeParameter>>) -> String {
+            p.tag.join(",")
+        }
+
+        check(Router::new().route("/", get(garde))).await
+    }
+
+    #[cfg(feature = "validify")]
+    #[tokio::test]
+    async fn nested_query_validify() -> anyhow::Result<()> {
+        use crate::{Validated, Validified};
+        use axum::routing::get;
+        use axum::Router;
+        use validify::{Payload, Validify};
+
+        #[derive(Deserialize, Validify, Payload)]
+        struct ValidifyParameter {
+            #[validate(length(max = 2))]
+            #[serde(default)]
+            tag: Vec<String>,
+            #[validify]
+            filter: ValidifyFilter,
+            #[allow(dead_code)]
+            page: Option<u32>,
+        }
+
+        #[derive(Deserialize, Validify, Payload)]
+        struct ValidifyFilter {
+            #[modify(trim)]
+            #[validate(length(min = 1))]
+            name: String,
+            #[serde(default)]
+            #[allow(dead_code)]
+            tags: Vec<String>,
+        }
+
+        async fn validated(
+            Validated(NestedQuery(p)): Validated<NestedQuery<ValidifyParameter>>,
+        ) -> String {
+            p.tag.join(",")
+        }
+
+        async fn validified(
+            Validified(NestedQuery(p)): Validified<NestedQuery<ValidifyParameter>>,
+        ) -> String {
+            p.filter.name
+        }
+
+        check(Router::new().route("/", get(validated))).await?;
+        check(Router::new().route("/", get(validified))).await
+    }
+}
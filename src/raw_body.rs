@@ -0,0 +1,543 @@
+//! # Constraints for raw request bodies
+//!
+//! ## Feature
+//!
+//! Enable the `raw_body` feature to use `Valid<RawBody<C>>`, `Garde<RawBody<C>>` or `Validated<RawBody<C>>`.
+//!
+//! ## Rules
+//!
+//! Body constraints are declared by implementing `BodyRules` for a marker type `C`:
+//! the minimum and maximum size, whether the body must be valid UTF-8, and a custom check of the content
+//! and the request head, e.g. a regular expression or a signature header.
+//! Keys needed by the check can be provided through the request extensions, e.g. with `Extension`.
+//!
+//! The maximum size is enforced while streaming: reading stops as soon as it is exceeded,
+//! without buffering the rest of the body. Bodies exceeding the `DefaultBodyLimit` are still
+//! rejected with `413 Payload Too Large`.
+//!
+//! ## Usage
+//!
+//! 1. Declare the constraints by implementing `BodyRules` for a marker type `C`.
+//! 2. In your handler function, use `Valid<RawBody<C>>` (or `Garde`, `Validated`) as some parameter's type.
+//! 3. Read the body with `bytes` or, if `BodyRules::UTF8` is set, with `text`.
+//!
+//! Violations are reported as validation errors of the `body` field, with the codes
+//! `min_size`, `max_size`, `utf8` and `check`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::http::request::Parts;
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_valid::raw_body::{BodyRules, RawBody};
+//!     use axum_valid::Valid;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/webhook", post(handler))
+//!     }
+//!
+//!     pub struct Webhook;
+//!
+//!     impl BodyRules for Webhook {
+//!         const MIN_SIZE: usize = 1;
+//!         const MAX_SIZE: Option<usize> = Some(64 * 1024);
+//!         const UTF8: bool = true;
+//!
+//!         fn check(parts: &Parts, body: &[u8]) -> bool {
+//!             parts.headers.contains_key("x-event-id") && body.starts_with(b"event:")
+//!         }
+//!     }
+//!
+//!     async fn handler(Valid(body): Valid<RawBody<Webhook>>) {
+//!         assert!(body.text().is_some_and(|text| text.starts_with("event:")));
+//!     }
+//! }
+//!
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::routing::post;
+//!     use axum::Router;
+//!     use axum_valid::raw_body::{BodyRules, RawBody};
+//!     use axum_valid::Garde;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/webhook", post(handler))
+//!     }
+//!
+//!     pub struct Webhook;
+//!
+//!     impl BodyRules for Webhook {
+//!         const MIN_SIZE: usize = 1;
+//!         const MAX_SIZE: Option<usize> = Some(64 * 1024);
+//!     }
+//!
+//!     async fn handler(Garde(body): Garde<RawBody<Webhook>>) {
+//!         assert!(!body.bytes().is_empty());
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::HasValidate;
+use axum::body::Bytes;
+use axum::extract::rejection::BytesRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::RequestExt;
+use futures_core::Stream;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+/// Trait for marker types that declare raw body constraints.
+///
+/// Every constraint is disabled by default.
+pub trait BodyRules {
+    /// Minimum size of the body in bytes, `1` requires a non-empty body
+    const MIN_SIZE: usize = 0;
+    /// Maximum size of the body in bytes
+    const MAX_SIZE: Option<usize> = None;
+    /// Whether the body must be valid UTF-8
+    const UTF8: bool = false;
+
+    /// Custom check of the body content and the request head, e.g. a regular expression or a signature header
+    ///
+    /// Only called if the body doesn't exceed `MAX_SIZE`.
+    fn check(_parts: &Parts, _body: &[u8]) -> bool {
+        true
+    }
+}
+
+/// Violation of a raw body constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BodyViolation {
+    /// The body is smaller than `BodyRules::MIN_SIZE`.
+    MinSize {
+        /// Minimum size in bytes
+        min: usize,
+    },
+    /// The body is larger than `BodyRules::MAX_SIZE`.
+    MaxSize {
+        /// Maximum size in bytes
+        max: usize,
+    },
+    /// The body is not valid UTF-8 although `BodyRules::UTF8` is set.
+    Utf8,
+    /// The body is rejected by `BodyRules::check`.
+    Check,
+}
+
+impl BodyViolation {
+    /// Error code of the violation
+    pub fn code(&self) -> &'static str {
+        match self {
+            BodyViolation::MinSize { .. } => "min_size",
+            BodyViolation::MaxSize { .. } => "max_size",
+            BodyViolation::Utf8 => "utf8",
+            BodyViolation::Check => "check",
+        }
+    }
+}
+
+impl Display for BodyViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BodyViolation::MinSize { min } => write!(f, "body is smaller than {min} bytes"),
+            BodyViolation::MaxSize { max } => write!(f, "body is larger than {max} bytes"),
+            BodyViolation::Utf8 => write!(f, "body is not valid UTF-8"),
+            BodyViolation::Check => write!(f, "body is invalid"),
+        }
+    }
+}
+
+impl std::error::Error for BodyViolation {}
+
+/// # `RawBody<C>` extractor
+///
+/// Buffers the raw request body and checks it against the constraints of `C` when validated,
+/// see the [module documentation](self).
+pub struct RawBody<C> {
+    bytes: Bytes,
+    exceeded: bool,
+    checked: bool,
+    rules: PhantomData<C>,
+}
+
+impl<C> Debug for RawBody<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawBody")
+            .field("bytes", &self.bytes)
+            .field("exceeded", &self.exceeded)
+            .field("checked", &self.checked)
+            .finish()
+    }
+}
+
+impl<C> Clone for RawBody<C> {
+    fn clone(&self) -> Self {
+        RawBody {
+            bytes: self.bytes.clone(),
+            exceeded: self.exceeded,
+            checked: self.checked,
+            rules: PhantomData,
+        }
+    }
+}
+
+impl<C> RawBody<C> {
+    /// Returns the body, truncated to `BodyRules::MAX_SIZE` if it is larger.
+    pub fn bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+
+    /// Returns the body as a string slice, if it is valid UTF-8.
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.bytes).ok()
+    }
+
+    /// Consumes the `RawBody` and returns the body.
+    pub fn into_inner(self) -> Bytes {
+        self.bytes
+    }
+}
+
+impl<C: BodyRules> RawBody<C> {
+    /// Returns the violated constraints, empty if the body is valid.
+    pub fn violations(&self) -> Vec<BodyViolation> {
+        let mut violations = Vec::new();
+        if let Some(max) = C::MAX_SIZE.filter(|_| self.exceeded) {
+            violations.push(BodyViolation::MaxSize { max });
+            return violations;
+        }
+        if self.bytes.len() < C::MIN_SIZE {
+            violations.push(BodyViolation::MinSize { min: C::MIN_SIZE });
+        }
+        if C::UTF8 && self.text().is_none() {
+            violations.push(BodyViolation::Utf8);
+        }
+        if !self.checked {
+            violations.push(BodyViolation::Check);
+        }
+        violations
+    }
+}
+
+/// Rejection used for `RawBody<C>` when the body can't be read.
+#[derive(Debug)]
+pub enum RawBodyRejection {
+    /// Failed to buffer the body without a maximum size.
+    Bytes(BytesRejection),
+    /// Failed to read the body while enforcing the maximum size.
+    ///
+    /// Responds with `400 Bad Request`.
+    Body(axum::Error),
+    /// The body exceeds the `DefaultBodyLimit` (or another length limit) while enforcing the maximum size.
+    ///
+    /// Responds with `413 Payload Too Large`, like `Bytes`.
+    LengthLimit(axum::Error),
+}
+
+impl RawBodyRejection {
+    fn from_body_error(error: axum::Error) -> Self {
+        let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&error);
+        while let Some(inner) = source {
+            if inner.is::<http_body_util::LengthLimitError>() {
+                return RawBodyRejection::LengthLimit(error);
+            }
+            source = inner.source();
+        }
+        RawBodyRejection::Body(error)
+    }
+}
+
+impl Display for RawBodyRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RawBodyRejection::Bytes(rejection) => write!(f, "{rejection}"),
+            RawBodyRejection::Body(error) => write!(f, "Failed to read the request body: {error}"),
+            RawBodyRejection::LengthLimit(error) => {
+                write!(f, "Failed to buffer the request body: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RawBodyRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RawBodyRejection::Bytes(rejection) => Some(rejection),
+            RawBodyRejection::Body(error) | RawBodyRejection::LengthLimit(error) => Some(error),
+        }
+    }
+}
+
+impl IntoResponse for RawBodyRejection {
+    fn into_response(self) -> Response {
+        match self {
+            RawBodyRejection::Bytes(rejection) => rejection.into_response(),
+            RawBodyRejection::Body(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+            }
+            RawBodyRejection::LengthLimit(_) => {
+                (StatusCode::PAYLOAD_TOO_LARGE, self.to_string()).into_response()
+            }
+        }
+    }
+}
+
+impl<State, C> FromRequest<State> for RawBody<C>
+where
+    State: Send + Sync,
+    C: BodyRules,
+{
+    type Rejection = RawBodyRejection;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let (parts, body) = req.into_parts();
+        let req = Request::from_parts(parts.clone(), body);
+        let Some(max) = C::MAX_SIZE else {
+            let bytes = Bytes::from_request(req, state)
+                .await
+                .map_err(RawBodyRejection::Bytes)?;
+            return Ok(RawBody {
+                checked: C::check(&parts, &bytes),
+                bytes,
+                exceeded: false,
+                rules: PhantomData,
+            });
+        };
+        let mut stream = req.into_limited_body().into_data_stream();
+        let mut buffer = Vec::new();
+        let mut exceeded = false;
+        while let Some(chunk) = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await
+        {
+            let chunk = chunk.map_err(RawBodyRejection::from_body_error)?;
+            if buffer.len() + chunk.len() > max {
+                buffer.extend_from_slice(&chunk[..max - buffer.len()]);
+                exceeded = true;
+                break;
+            }
+            buffer.extend_from_slice(&chunk);
+        }
+        Ok(RawBody {
+            checked: exceeded || C::check(&parts, &buffer),
+            bytes: Bytes::from(buffer),
+            exceeded,
+            rules: PhantomData,
+        })
+    }
+}
+
+impl<C> HasValidate for RawBody<C> {
+    type Validate = Self;
+
+    fn get_validate(&self) -> &Self::Validate {
+        self
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<C: BodyRules> validator::Validate for RawBody<C> {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        let violations = self.violations();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let mut errors = validator::ValidationErrors::new();
+        for violation in violations {
+            let mut error = validator::ValidationError::new(violation.code())
+                .with_message(violation.to_string().into());
+            match violation {
+                BodyViolation::MinSize { min } => error.add_param("min".into(), &min),
+                BodyViolation::MaxSize { max } => error.add_param("max".into(), &max),
+                BodyViolation::Utf8 | BodyViolation::Check => {}
+            }
+            errors.add("body", error);
+        }
+        Err(errors)
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<C: BodyRules> garde::Validate for RawBody<C> {
+    type Context = ();
+
+    fn validate_into(
+        &self,
+        _ctx: &Self::Context,
+        parent: &mut dyn FnMut() -> garde::Path,
+        report: &mut garde::Report,
+    ) {
+        for violation in self.violations() {
+            report.append(
+                parent().join("body"),
+                garde::Error::new(violation.to_string()),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<C: BodyRules> validify::Validate for RawBody<C> {
+    fn validate(&self) -> Result<(), validify::ValidationErrors> {
+        let violations = self.violations();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let mut errors = validify::ValidationErrors::new();
+        for violation in violations {
+            errors.add(
+                validify::ValidationError::new_field_named("body", violation.code())
+                    .with_message(violation.to_string()),
+            );
+        }
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    struct Webhook;
+
+    impl BodyRules for Webhook {
+        const MIN_SIZE: usize = 1;
+        const MAX_SIZE: Option<usize> = Some(8);
+        const UTF8: bool = true;
+
+        fn check(parts: &Parts, body: &[u8]) -> bool {
+            !parts.headers.contains_key("x-reject") && body.starts_with(b"ev")
+        }
+    }
+
+    struct Unlimited;
+
+    impl BodyRules for Unlimited {}
+
+    async fn extract<C: BodyRules>(body: impl Into<Body>) -> anyhow::Result<RawBody<C>> {
+        let request = Request::builder().body(body.into())?;
+        Ok(RawBody::<C>::from_request(request, &()).await?)
+    }
+
+    #[tokio::test]
+    async fn raw_body_violations() -> anyhow::Result<()> {
+        let body = extract::<Webhook>("event").await?;
+        assert_eq!(body.text(), Some("event"));
+        assert!(body.violations().is_empty());
+
+        let body = extract::<Webhook>("").await?;
+        assert_eq!(
+            body.violations(),
+            vec![BodyViolation::MinSize { min: 1 }, BodyViolation::Check]
+        );
+
+        let body = extract::<Webhook>(vec![b'e', b'v', 0xff]).await?;
+        assert_eq!(body.violations(), vec![BodyViolation::Utf8]);
+
+        let body = extract::<Webhook>("event: too long").await?;
+        assert_eq!(body.bytes().as_ref(), b"event: t");
+        assert_eq!(body.violations(), vec![BodyViolation::MaxSize { max: 8 }]);
+
+        let body = extract::<Unlimited>("").await?;
+        assert!(body.violations().is_empty());
+
+        let request = Request::builder()
+            .header("x-reject", "1")
+            .body(Body::from("event"))?;
+        let body = RawBody::<Webhook>::from_request(request, &()).await?;
+        assert_eq!(body.violations(), vec![BodyViolation::Check]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn raw_body_length_limit() -> anyhow::Result<()> {
+        let body = Body::new(http_body_util::Limited::new(Body::from("event"), 4));
+        let rejection = extract::<Webhook>(body)
+            .await
+            .expect_err("length limit exceeded")
+            .downcast::<RawBodyRejection>()?;
+        assert!(matches!(rejection, RawBodyRejection::LengthLimit(_)));
+        assert_eq!(
+            rejection.into_response().status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "validator")]
+    #[tokio::test]
+    async fn valid_raw_body() -> anyhow::Result<()> {
+        use crate::{Valid, ValidRejection};
+
+        let request = Request::builder().body(Body::from("event"))?;
+        assert!(Valid::<RawBody<Webhook>>::from_request(request, &())
+            .await
+            .is_ok());
+
+        let request = Request::builder().body(Body::from("nothing"))?;
+        let Err(ValidRejection::Valid(errors)) =
+            Valid::<RawBody<Webhook>>::from_request(request, &()).await
+        else {
+            anyhow::bail!("expected validation errors");
+        };
+        assert_eq!(errors.field_errors()["body"][0].code, "check");
+        Ok(())
+    }
+
+    #[cfg(feature = "garde")]
+    #[tokio::test]
+    async fn garde_raw_body() -> anyhow::Result<()> {
+        use crate::{Garde, GardeRejection};
+
+        let request = Request::builder().body(Body::from(""))?;
+        let Err(GardeRejection::Valid(report)) =
+            Garde::<RawBody<Webhook>>::from_request(request, &()).await
+        else {
+            anyhow::bail!("expected validation errors");
+        };
+        assert_eq!(report.iter().count(), 2);
+        Ok(())
+    }
+
+    #[cfg(feature = "validify")]
+    #[tokio::test]
+    async fn validated_raw_body() -> anyhow::Result<()> {
+        use crate::{Validated, ValidifyRejection};
+
+        let request = Request::builder().body(Body::from("event"))?;
+        assert!(Validated::<RawBody<Webhook>>::from_request(request, &())
+            .await
+            .is_ok());
+
+        let request = Request::builder().body(Body::from("event: too long"))?;
+        let Err(ValidifyRejection::Valid(errors)) =
+            Validated::<RawBody<Webhook>>::from_request(request, &()).await
+        else {
+            anyhow::bail!("expected validation errors");
+        };
+        assert_eq!(errors.errors().len(), 1);
+        Ok(())
+    }
+}
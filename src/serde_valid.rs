@@ -0,0 +1,311 @@
+//! # Serde valid support
+//!
+//! ## Feature
+//!
+//! Enable the `serde_valid` feature to use `SerdeValid<E>`.
+//!
+//! ## Usage
+//!
+//! 1. Implement `Deserialize` and `serde_valid::Validate` for your data type `T`.
+//! 2. In your handler function, use `SerdeValid<E>` as some parameter's type, where `E` is any extractor
+//!    implementing `HasValidate`, e.g. `SerdeValid<Json<T>>` or `SerdeValid<Query<T>>`.
+//!
+//! Validation errors are collected into `serde_valid::validation::Errors`, which is serializable,
+//! so enable the `into_json` feature to return them as a JSON body.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use axum::extract::Query;
+//! use axum::routing::{get, post};
+//! use axum::{Json, Router};
+//! use axum_valid::SerdeValid;
+//! use serde::Deserialize;
+//! use serde_valid::Validate;
+//!
+//! #[derive(Debug, Deserialize, Validate)]
+//! pub struct Paginator {
+//!     #[validate(minimum = 1)]
+//!     #[validate(maximum = 50)]
+//!     pub page_size: usize,
+//!     #[validate(minimum = 1)]
+//!     pub page_no: usize,
+//! }
+//!
+//! async fn paginator_from_query(SerdeValid(Query(paginator)): SerdeValid<Query<Paginator>>) {
+//!     assert!((1..=50).contains(&paginator.page_size));
+//!     assert!((1..).contains(&paginator.page_no));
+//! }
+//!
+//! async fn paginator_from_json(paginator: SerdeValid<Json<Paginator>>) {
+//!     assert!(paginator.validate().is_ok());
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use tokio::net::TcpListener;
+//! let router = Router::new()
+//!     .route("/query", get(paginator_from_query))
+//!     .route("/json", post(paginator_from_json));
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::{HasValidate, ValidationRejection};
+use ::serde_valid::validation::Errors;
+use ::serde_valid::Validate;
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// # `SerdeValid` data extractor
+///
+/// SerdeValid uses serde_valid to validate data, its usage is similar to `Valid`.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SerdeValid<E>(pub E);
+
+impl<E> Deref for SerdeValid<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for SerdeValid<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display> Display for SerdeValid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E> SerdeValid<E> {
+    /// Consumes the `SerdeValid` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T> aide::OperationInput for SerdeValid<T>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+/// `SerdeValidRejection` is returned when the `SerdeValid` extractor fails.
+///
+/// The validation errors are serializable, and are returned as a JSON body with the `into_json` feature.
+///
+pub type SerdeValidRejection<E> = ValidationRejection<Errors, E>;
+
+impl<E> From<Errors> for SerdeValidRejection<E> {
+    fn from(value: Errors) -> Self {
+        Self::Valid(value)
+    }
+}
+
+impl<State, Extractor> FromRequest<State> for SerdeValid<Extractor>
+where
+    State: Send + Sync,
+    Extractor: HasValidate + FromRequest<State>,
+    Extractor::Validate: Validate,
+{
+    type Rejection = SerdeValidRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(SerdeValidRejection::Inner)?;
+        inner.get_validate().validate()?;
+        Ok(SerdeValid(inner))
+    }
+}
+
+impl<State, Extractor> FromRequestParts<State> for SerdeValid<Extractor>
+where
+    State: Send + Sync,
+    Extractor: HasValidate + FromRequestParts<State>,
+    Extractor::Validate: Validate,
+{
+    type Rejection = SerdeValidRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(SerdeValidRejection::Inner)?;
+        inner.get_validate().validate()?;
+        Ok(SerdeValid(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::error::Error;
+    use std::io;
+
+    const SERDE_VALID: &str = "serde_valid";
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct Parameters {
+        #[validate(minimum = 5)]
+        #[validate(maximum = 10)]
+        v0: i32,
+        #[validate(min_length = 1)]
+        #[validate(max_length = 10)]
+        v1: String,
+    }
+
+    fn errors() -> Errors {
+        Parameters {
+            v0: 0,
+            v1: String::new(),
+        }
+        .validate()
+        .expect_err("invalid parameters")
+    }
+
+    #[test]
+    fn serde_valid_deref_deref_mut_into_inner() {
+        let mut inner = String::from(SERDE_VALID);
+        let mut v = SerdeValid(inner.clone());
+        assert_eq!(&inner, v.deref());
+        inner.push_str(SERDE_VALID);
+        v.deref_mut().push_str(SERDE_VALID);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[test]
+    fn serialize_errors() -> anyhow::Result<()> {
+        let value = serde_json::to_value(errors())?;
+        assert!(value["properties"]["v0"]["errors"].is_array());
+        assert!(value["properties"]["v1"]["errors"].is_array());
+        Ok(())
+    }
+
+    #[cfg(feature = "query")]
+    #[tokio::test]
+    async fn serde_valid_query() {
+        use axum::extract::Query;
+
+        async fn extract(uri: &'static str) -> Result<Parameters, SerdeValidRejection<String>> {
+            let (mut parts, _) = Request::builder()
+                .uri(uri)
+                .body(())
+                .expect("build request")
+                .into_parts();
+            SerdeValid::<Query<Parameters>>::from_request_parts(&mut parts, &())
+                .await
+                .map(|SerdeValid(Query(parameters))| parameters)
+                .map_err(|rejection| match rejection {
+                    ValidationRejection::Valid(errors) => ValidationRejection::Valid(errors),
+                    ValidationRejection::Inner(inner) => {
+                        ValidationRejection::Inner(inner.body_text())
+                    }
+                })
+        }
+
+        let parameters = extract("/?v0=5&v1=0123456789").await.expect("valid query");
+        assert_eq!(parameters.v0, 5);
+        assert_eq!(parameters.v1, "0123456789");
+        assert!(matches!(
+            extract("/?v0=11&v1=0123456789").await,
+            Err(SerdeValidRejection::Valid(_))
+        ));
+        assert!(matches!(
+            extract("/?v0=5").await,
+            Err(SerdeValidRejection::Inner(_))
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn serde_valid_json() -> anyhow::Result<()> {
+        use crate::VALIDATION_ERROR_STATUS;
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use reqwest::Client;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+
+        async fn handler(SerdeValid(Json(parameters)): SerdeValid<Json<Parameters>>) -> String {
+            parameters.v1
+        }
+
+        let router = Router::new().route("/", post(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "v0": 6, "v1": "valid" }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "valid");
+
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "v0": 6, "v1": "" }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        #[cfg(feature = "into_json")]
+        assert!(response.json::<serde_json::Value>().await?["properties"]["v1"].is_object());
+
+        let response = client.post(&url).body("{}").send().await?;
+        assert_ne!(response.status(), StatusCode::OK);
+        assert_ne!(response.status(), VALIDATION_ERROR_STATUS);
+        Ok(())
+    }
+
+    #[test]
+    fn display_error() {
+        // SerdeValidRejection::Valid Display
+        let s = errors().to_string();
+        let vr = SerdeValidRejection::<String>::Valid(errors());
+        assert_eq!(vr.to_string(), s);
+
+        // SerdeValidRejection::Inner Display
+        let inner = String::from(SERDE_VALID);
+        let vr = SerdeValidRejection::<String>::Inner(inner.clone());
+        assert_eq!(inner.to_string(), vr.to_string());
+
+        // SerdeValidRejection::Valid Error
+        let vr = SerdeValidRejection::<io::Error>::Valid(errors());
+        assert!(matches!(vr.source(), Some(source) if source.downcast_ref::<Errors>().is_some()));
+
+        // SerdeValidRejection::Inner Error
+        let vr = SerdeValidRejection::<io::Error>::Inner(io::Error::other(SERDE_VALID));
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<io::Error>().is_some())
+        );
+    }
+}
@@ -0,0 +1,665 @@
+//! # Shadow-mode validation
+//!
+//! ## Feature
+//!
+//! Enable the `shadow` feature to use `Shadow<V, M>`.
+//!
+//! ## Usage
+//!
+//! When tightening the rules of a live API, it is safer to first observe how many real requests would fail.
+//!
+//! `Shadow<V, M>` wraps a validation extractor `V`, which is `Valid<E>`, `Garde<E>` or `Validated<E>`.
+//! The data is extracted and validated as usual, but in shadow mode validation failures are only reported,
+//! and the request proceeds with the invalid data. Errors of the inner extractor are returned as usual.
+//!
+//! The marker type `M` decides whether shadow mode is on and how failures are reported:
+//!
+//! * [`ShadowAlways`] (default): shadow mode is always on, for the routes using it.
+//!   Failures are reported as `tracing` events.
+//! * [`ShadowFromState`]: shadow mode is switched at runtime by the [`ShadowSwitch`] taken from the state.
+//!   Failures are counted, passed to the switch's hook, and reported as `tracing` events.
+//!   When the switch is off, failures are rejected as usual.
+//!
+//! Implement `ShadowMode` for your own marker type to use another mechanism.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::extract::FromRef;
+//!     use axum::routing::post;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::shadow::{Shadow, ShadowFromState, ShadowSwitch};
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     #[derive(Clone, FromRef)]
+//!     pub struct AppState {
+//!         shadow: ShadowSwitch,
+//!     }
+//!
+//!     pub fn router() -> Router {
+//!         // Call `set_enabled(false)` on a clone of the switch to start enforcing the rules
+//!         let shadow = ShadowSwitch::with_hook(true, |failure| {
+//!             println!("{} {} would be rejected: {}", failure.method, failure.uri, failure.errors);
+//!         });
+//!         Router::new()
+//!             .route("/json", post(handler))
+//!             .route("/json_always", post(always_handler))
+//!             .with_state(AppState { shadow })
+//!     }
+//!
+//!     async fn handler(
+//!         Shadow(Valid(Json(parameter)), _): Shadow<Valid<Json<Parameter>>, ShadowFromState>,
+//!     ) {
+//!         // May be invalid if the switch is on
+//!         println!("v0 = {}, v1 = {}", parameter.v0, parameter.v1);
+//!     }
+//!
+//!     async fn always_handler(Shadow(Valid(Json(parameter)), _): Shadow<Valid<Json<Parameter>>>) {
+//!         // May be invalid
+//!         println!("v0 = {}, v1 = {}", parameter.v0, parameter.v1);
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[validate(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+#[cfg(any(feature = "validator", feature = "garde", feature = "validify"))]
+use crate::HasValidate;
+use axum::extract::FromRef;
+#[cfg(any(feature = "validator", feature = "garde", feature = "validify"))]
+use axum::extract::{FromRequest, FromRequestParts, Request};
+#[cfg(any(feature = "validator", feature = "garde", feature = "validify"))]
+use axum::http::request::Parts;
+use axum::http::{Method, Uri};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// A validation failure let through in shadow mode.
+#[derive(Debug)]
+pub struct ShadowFailure<'a> {
+    /// Method of the request
+    pub method: &'a Method,
+    /// URI of the request
+    pub uri: &'a Uri,
+    /// Type name of the validated data
+    pub data: &'static str,
+    /// Validation errors
+    pub errors: &'a (dyn Error + 'static),
+}
+
+impl ShadowFailure<'_> {
+    /// Reports the failure as a `tracing` event at the `WARN` level.
+    pub fn trace(&self) {
+        tracing::warn!(
+            method = %self.method,
+            uri = %self.uri,
+            data = self.data,
+            errors = %self.errors,
+            "validation failed in shadow mode"
+        );
+    }
+}
+
+type Hook = dyn Fn(&ShadowFailure<'_>) + Send + Sync;
+
+struct ShadowSwitchInner {
+    enabled: AtomicBool,
+    failures: AtomicU64,
+    hook: Option<Box<Hook>>,
+}
+
+/// Runtime switch for shadow mode, shared through state, see [`ShadowFromState`].
+///
+/// Clones share the same switch, so it can be turned on and off while the router is serving.
+#[derive(Clone)]
+pub struct ShadowSwitch(Arc<ShadowSwitchInner>);
+
+impl ShadowSwitch {
+    /// Creates a switch, with shadow mode on if `enabled`.
+    pub fn new(enabled: bool) -> Self {
+        Self(Arc::new(ShadowSwitchInner {
+            enabled: AtomicBool::new(enabled),
+            failures: AtomicU64::new(0),
+            hook: None,
+        }))
+    }
+
+    /// Creates a switch with a hook called for every failure let through in shadow mode.
+    pub fn with_hook<F>(enabled: bool, hook: F) -> Self
+    where
+        F: Fn(&ShadowFailure<'_>) + Send + Sync + 'static,
+    {
+        Self(Arc::new(ShadowSwitchInner {
+            enabled: AtomicBool::new(enabled),
+            failures: AtomicU64::new(0),
+            hook: Some(Box::new(hook)),
+        }))
+    }
+
+    /// Returns `true` if shadow mode is on.
+    pub fn is_enabled(&self) -> bool {
+        self.0.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Turns shadow mode on or off.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.0.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns the number of failures let through in shadow mode.
+    pub fn failures(&self) -> u64 {
+        self.0.failures.load(Ordering::Relaxed)
+    }
+
+    fn report(&self, failure: &ShadowFailure<'_>) {
+        self.0.failures.fetch_add(1, Ordering::Relaxed);
+        if let Some(hook) = &self.0.hook {
+            hook(failure);
+        }
+        failure.trace();
+    }
+}
+
+impl Debug for ShadowSwitch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShadowSwitch")
+            .field("enabled", &self.is_enabled())
+            .field("failures", &self.failures())
+            .field("hook", &self.0.hook.is_some())
+            .finish()
+    }
+}
+
+/// Trait for marker types that decide whether shadow mode is on and report failures.
+///
+pub trait ShadowMode<State> {
+    /// Returns `true` if validation failures should be let through
+    fn is_shadow(state: &State) -> bool;
+    /// Reports a validation failure let through
+    fn report(state: &State, failure: &ShadowFailure<'_>);
+}
+
+/// Shadow mode is always on, failures are reported as `tracing` events.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShadowAlways;
+
+impl<State> ShadowMode<State> for ShadowAlways {
+    fn is_shadow(_: &State) -> bool {
+        true
+    }
+
+    fn report(_: &State, failure: &ShadowFailure<'_>) {
+        failure.trace();
+    }
+}
+
+/// Shadow mode is switched by the [`ShadowSwitch`] taken from the state.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShadowFromState;
+
+impl<State> ShadowMode<State> for ShadowFromState
+where
+    ShadowSwitch: FromRef<State>,
+{
+    fn is_shadow(state: &State) -> bool {
+        ShadowSwitch::from_ref(state).is_enabled()
+    }
+
+    fn report(state: &State, failure: &ShadowFailure<'_>) {
+        ShadowSwitch::from_ref(state).report(failure);
+    }
+}
+
+/// # `Shadow` data extractor
+///
+/// `Shadow` lets validation failures of the inner validation extractor through while shadow mode is on,
+/// see the [module documentation](self).
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Shadow<V, M = ShadowAlways>(pub V, pub PhantomData<M>);
+
+impl<V, M> Deref for Shadow<V, M> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<V, M> DerefMut for Shadow<V, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display, M> Display for Shadow<T, M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<V, M> Shadow<V, M> {
+    /// Consumes the `Shadow` and returns the validation extractor within.
+    pub fn into_inner(self) -> V {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T, M> aide::OperationInput for Shadow<T, M>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+/// Lets the validation errors through if shadow mode is on, reporting them.
+#[cfg(any(feature = "validator", feature = "garde", feature = "validify"))]
+fn shadow<State, Mode, Data, E>(
+    state: &State,
+    method: &Method,
+    uri: &Uri,
+    result: Result<(), E>,
+) -> Result<(), E>
+where
+    Mode: ShadowMode<State>,
+    Data: ?Sized,
+    E: Error + 'static,
+{
+    match result {
+        Err(errors) if Mode::is_shadow(state) => {
+            Mode::report(
+                state,
+                &ShadowFailure {
+                    method,
+                    uri,
+                    data: std::any::type_name::<Data>(),
+                    errors: &errors,
+                },
+            );
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<State, Extractor, Mode> FromRequest<State> for Shadow<crate::Valid<Extractor>, Mode>
+where
+    State: Send + Sync,
+    Mode: ShadowMode<State>,
+    Extractor: HasValidate + FromRequest<State>,
+    Extractor::Validate: validator::Validate,
+{
+    type Rejection = crate::ValidRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let (method, uri) = (req.method().clone(), req.uri().clone());
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(crate::ValidRejection::Inner)?;
+        shadow::<State, Mode, Extractor::Validate, _>(
+            state,
+            &method,
+            &uri,
+            validator::Validate::validate(inner.get_validate()),
+        )?;
+        Ok(Shadow(crate::Valid(inner), PhantomData))
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<State, Extractor, Mode> FromRequestParts<State> for Shadow<crate::Valid<Extractor>, Mode>
+where
+    State: Send + Sync,
+    Mode: ShadowMode<State>,
+    Extractor: HasValidate + FromRequestParts<State>,
+    Extractor::Validate: validator::Validate,
+{
+    type Rejection = crate::ValidRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(crate::ValidRejection::Inner)?;
+        shadow::<State, Mode, Extractor::Validate, _>(
+            state,
+            &parts.method,
+            &parts.uri,
+            validator::Validate::validate(inner.get_validate()),
+        )?;
+        Ok(Shadow(crate::Valid(inner), PhantomData))
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<State, Extractor, Context, Mode> FromRequest<State> for Shadow<crate::Garde<Extractor>, Mode>
+where
+    State: Send + Sync,
+    Context: Send + Sync + FromRef<State>,
+    Mode: ShadowMode<State>,
+    Extractor: HasValidate + FromRequest<State>,
+    Extractor::Validate: garde::Validate<Context = Context>,
+{
+    type Rejection = crate::GardeRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let context = Context::from_ref(state);
+        let (method, uri) = (req.method().clone(), req.uri().clone());
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(crate::GardeRejection::Inner)?;
+        shadow::<State, Mode, Extractor::Validate, _>(
+            state,
+            &method,
+            &uri,
+            garde::Validate::validate_with(inner.get_validate(), &context),
+        )?;
+        Ok(Shadow(crate::Garde(inner), PhantomData))
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<State, Extractor, Context, Mode> FromRequestParts<State>
+    for Shadow<crate::Garde<Extractor>, Mode>
+where
+    State: Send + Sync,
+    Context: Send + Sync + FromRef<State>,
+    Mode: ShadowMode<State>,
+    Extractor: HasValidate + FromRequestParts<State>,
+    Extractor::Validate: garde::Validate<Context = Context>,
+{
+    type Rejection = crate::GardeRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let context = Context::from_ref(state);
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(crate::GardeRejection::Inner)?;
+        shadow::<State, Mode, Extractor::Validate, _>(
+            state,
+            &parts.method,
+            &parts.uri,
+            garde::Validate::validate_with(inner.get_validate(), &context),
+        )?;
+        Ok(Shadow(crate::Garde(inner), PhantomData))
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<State, Extractor, Mode> FromRequest<State> for Shadow<crate::Validated<Extractor>, Mode>
+where
+    State: Send + Sync,
+    Mode: ShadowMode<State>,
+    Extractor: HasValidate + FromRequest<State>,
+    Extractor::Validate: validify::Validate,
+{
+    type Rejection = crate::ValidifyRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let (method, uri) = (req.method().clone(), req.uri().clone());
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(crate::ValidifyRejection::Inner)?;
+        shadow::<State, Mode, Extractor::Validate, _>(
+            state,
+            &method,
+            &uri,
+            validify::Validate::validate(inner.get_validate()),
+        )?;
+        Ok(Shadow(crate::Validated(inner), PhantomData))
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<State, Extractor, Mode> FromRequestParts<State> for Shadow<crate::Validated<Extractor>, Mode>
+where
+    State: Send + Sync,
+    Mode: ShadowMode<State>,
+    Extractor: HasValidate + FromRequestParts<State>,
+    Extractor::Validate: validify::Validate,
+{
+    type Rejection = crate::ValidifyRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(crate::ValidifyRejection::Inner)?;
+        shadow::<State, Mode, Extractor::Validate, _>(
+            state,
+            &parts.method,
+            &parts.uri,
+            validify::Validate::validate(inner.get_validate()),
+        )?;
+        Ok(Shadow(crate::Validated(inner), PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::Mutex;
+
+    const SHADOW: &str = "shadow";
+
+    #[test]
+    fn shadow_deref_deref_mut_into_inner() {
+        let mut inner = String::from(SHADOW);
+        let mut v = Shadow::<_>(inner.clone(), PhantomData);
+        assert_eq!(&inner, v.deref());
+        inner.push_str(SHADOW);
+        v.deref_mut().push_str(SHADOW);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[cfg(any(feature = "validator", feature = "garde", feature = "validify"))]
+    #[test]
+    fn shadow_switch() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let switch = ShadowSwitch::with_hook(true, {
+            let reported = reported.clone();
+            move |failure| {
+                reported
+                    .lock()
+                    .expect("lock")
+                    .push(failure.errors.to_string());
+            }
+        });
+        let (method, uri) = (Method::POST, Uri::from_static("/"));
+        let error = || io::Error::other(SHADOW);
+
+        assert!(
+            shadow::<_, ShadowFromState, String, _>(&switch, &method, &uri, Err(error())).is_ok()
+        );
+        assert!(
+            shadow::<_, ShadowFromState, String, io::Error>(&switch, &method, &uri, Ok(())).is_ok()
+        );
+        assert_eq!(switch.failures(), 1);
+        assert_eq!(*reported.lock().expect("lock"), [SHADOW]);
+
+        switch.clone().set_enabled(false);
+        assert!(!switch.is_enabled());
+        assert!(
+            shadow::<_, ShadowFromState, String, _>(&switch, &method, &uri, Err(error())).is_err()
+        );
+        assert_eq!(switch.failures(), 1);
+
+        assert!(shadow::<_, ShadowAlways, String, _>(&(), &method, &uri, Err(error())).is_ok());
+        println!("{:?}", switch);
+    }
+
+    #[cfg(all(feature = "validator", feature = "json"))]
+    #[tokio::test]
+    async fn shadow_valid() -> anyhow::Result<()> {
+        use crate::{Valid, VALIDATION_ERROR_STATUS};
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Json, Router};
+        use reqwest::Client;
+        use serde::Deserialize;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+        use validator::Validate;
+
+        #[derive(Debug, Deserialize, Validate)]
+        struct Parameters {
+            #[validate(range(max = 10))]
+            v0: i32,
+        }
+
+        async fn handler(
+            Shadow(Valid(Json(parameters)), _): Shadow<Valid<Json<Parameters>>, ShadowFromState>,
+        ) -> String {
+            parameters.v0.to_string()
+        }
+
+        let switch = ShadowSwitch::new(true);
+        let router = Router::new()
+            .route("/", post(handler))
+            .with_state(switch.clone());
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "v0": 20 }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "20");
+        assert_eq!(switch.failures(), 1);
+
+        switch.set_enabled(false);
+        let response = client
+            .post(&url)
+            .json(&serde_json::json!({ "v0": 20 }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        assert_eq!(switch.failures(), 1);
+
+        let response = client.post(&url).body("{}").send().await?;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "garde", feature = "query"))]
+    #[tokio::test]
+    async fn shadow_garde() {
+        use crate::{Garde, GardeRejection};
+        use axum::extract::Query;
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize, garde::Validate)]
+        struct Parameters {
+            #[garde(range(max = 10))]
+            v0: i32,
+        }
+
+        let (mut parts, _) = Request::builder()
+            .uri("/?v0=20")
+            .body(())
+            .expect("build request")
+            .into_parts();
+        let Shadow(Garde(Query(parameters)), _) =
+            Shadow::<Garde<Query<Parameters>>>::from_request_parts(&mut parts, &())
+                .await
+                .expect("shadowed failure");
+        assert_eq!(parameters.v0, 20);
+
+        #[derive(Clone, FromRef)]
+        struct State {
+            shadow: ShadowSwitch,
+            context: (),
+        }
+
+        let state = State {
+            shadow: ShadowSwitch::new(false),
+            context: (),
+        };
+        assert!(matches!(
+            Shadow::<Garde<Query<Parameters>>, ShadowFromState>::from_request_parts(
+                &mut parts, &state
+            )
+            .await,
+            Err(GardeRejection::Valid(_))
+        ));
+    }
+
+    #[cfg(all(feature = "validify", feature = "query"))]
+    #[tokio::test]
+    async fn shadow_validated() {
+        use crate::{Validated, ValidifyRejection};
+        use axum::extract::Query;
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize, validify::Validate)]
+        struct Parameters {
+            #[validate(range(max = 10.0))]
+            v0: i32,
+        }
+
+        let (mut parts, _) = Request::builder()
+            .uri("/?v0=20")
+            .body(())
+            .expect("build request")
+            .into_parts();
+        let switch = ShadowSwitch::new(true);
+        let Shadow(Validated(Query(parameters)), _) =
+            Shadow::<Validated<Query<Parameters>>, ShadowFromState>::from_request_parts(
+                &mut parts, &switch,
+            )
+            .await
+            .expect("shadowed failure");
+        assert_eq!(parameters.v0, 20);
+        assert_eq!(switch.failures(), 1);
+
+        switch.set_enabled(false);
+        assert!(matches!(
+            Shadow::<Validated<Query<Parameters>>, ShadowFromState>::from_request_parts(
+                &mut parts, &switch
+            )
+            .await,
+            Err(ValidifyRejection::Valid(_))
+        ));
+    }
+}
@@ -0,0 +1,768 @@
+//! # Rejecting unknown fields
+//!
+//! ## Feature
+//!
+//! Enable the `strict` feature to use `Strict<Json<T>, M>`, `Strict<Query<T>, M>` and `Strict<Form<T>, M>`
+//! (with the `json`, `query` and `form` features respectively).
+//!
+//! ## Usage
+//!
+//! Serde ignores unknown keys by default, so a typo in a payload (e.g. `emial`) silently leaves a field at its
+//! default value. `#[serde(deny_unknown_fields)]` fixes that, but it doesn't work together with `#[serde(flatten)]`,
+//! and it turns the typo into an opaque deserialization error.
+//!
+//! `Strict<E, M>` deserializes the data as `E` does, and rejects the request if any key was ignored during
+//! deserialization. The keys are reported by path as [`UnknownFields`], through the standard validation rejection,
+//! e.g. `{"user.emial":["unknown_field"]}` with the `into_json` feature. Errors of the inner extractor are returned
+//! as usual.
+//!
+//! The marker type `M` decides whether strict mode is on:
+//!
+//! * [`StrictAlways`] (default): strict mode is always on, for the extractors using it.
+//! * [`StrictFromState`]: strict mode is switched by the [`StrictConfig`] taken from the state, so it can be
+//!   enabled globally, for all extractors using this mode at once.
+//!
+//! `Strict` implements `HasValidate` and the `validify` traits, so `Valid<Strict<E>>`, `Garde<Strict<E>>`,
+//! `Validified<Strict<E>>` and so on reject unknown fields before validating the data. `Validified` checks the
+//! fields of the payload type.
+//!
+//! Keys buffered by a `#[serde(flatten)]` field are reported if none of the flattened fields consumes them, so a
+//! flattened struct is as strict as its parent. Keys collected into a flattened map are known. The keys nested in
+//! the value of a buffered key, and the keys of untagged enums, are not checked.
+//!
+//! In strict mode, the data is deserialized a second time to collect the unknown fields, and once more for each
+//! key buffered by a `#[serde(flatten)]` field, up to [`MAX_PROBES`] keys. Buffered keys beyond them are not
+//! checked, so a large payload can't make the extractor deserialize it an unbounded number of times.
+//!
+//! Only the `Json`, `Query` and `Form` extractors support strict mode. For other formats,
+//! [`UnknownFields::collect`] reports the unknown fields of any self-describing deserializer.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::extract::FromRef;
+//!     use axum::routing::post;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::strict::{Strict, StrictConfig, StrictFromState};
+//!     use axum_valid::Valid;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     #[derive(Clone, FromRef)]
+//!     pub struct AppState {
+//!         strict: StrictConfig,
+//!     }
+//!
+//!     pub fn router() -> Router {
+//!         Router::new()
+//!             .route("/json", post(handler))
+//!             .route("/json_state", post(state_handler))
+//!             .with_state(AppState {
+//!                 strict: StrictConfig { enabled: true },
+//!             })
+//!     }
+//!
+//!     async fn handler(Valid(Strict(Json(user), _)): Valid<Strict<Json<User>>>) {
+//!         assert!(user.validate().is_ok());
+//!     }
+//!
+//!     async fn state_handler(
+//!         Valid(Strict(Json(user), _)): Valid<Strict<Json<User>, StrictFromState>>,
+//!     ) {
+//!         assert!(user.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct User {
+//!         #[validate(email)]
+//!         pub email: String,
+//!         #[serde(default)]
+//!         pub newsletter: bool,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+mod trace;
+
+#[cfg(feature = "validator")]
+use crate::HasValidateArgs;
+use crate::{HasValidate, ValidationRejection};
+use axum::extract::FromRef;
+#[cfg(feature = "query")]
+use axum::extract::FromRequestParts;
+#[cfg(any(feature = "json", feature = "form"))]
+use axum::extract::{FromRequest, Request};
+#[cfg(any(feature = "json", feature = "query", feature = "form"))]
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use trace::{Trace, Traced};
+
+/// Error message reported for each unknown field.
+pub const UNKNOWN_FIELD: &str = "unknown_field";
+
+/// Maximum number of fields deserialized without a type hint, as `#[serde(flatten)]` fields buffer them, that are
+/// checked by [`UnknownFields::collect`], as the data is deserialized once more for each of them.
+pub const MAX_PROBES: usize = 32;
+
+/// Trait for marker types deciding whether [`Strict`] rejects unknown fields.
+pub trait StrictMode<State> {
+    /// Whether unknown fields are rejected
+    fn is_strict(state: &State) -> bool;
+}
+
+/// Strict mode marker, always rejecting unknown fields.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictAlways;
+
+impl<State> StrictMode<State> for StrictAlways {
+    fn is_strict(_: &State) -> bool {
+        true
+    }
+}
+
+/// Strict mode marker, rejecting unknown fields if the [`StrictConfig`] taken from the state is enabled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictFromState;
+
+impl<State> StrictMode<State> for StrictFromState
+where
+    StrictConfig: FromRef<State>,
+{
+    fn is_strict(state: &State) -> bool {
+        StrictConfig::from_ref(state).enabled
+    }
+}
+
+/// Strict mode configuration, shared through state, see [`StrictFromState`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictConfig {
+    /// Whether unknown fields are rejected
+    pub enabled: bool,
+}
+
+/// # `Strict` data extractor
+///
+/// `Strict` rejects data with unknown fields, see the [module documentation](self).
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Strict<E, M = StrictAlways>(pub E, pub PhantomData<M>);
+
+impl<E, M> Deref for Strict<E, M> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E, M> DerefMut for Strict<E, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display, M> Display for Strict<T, M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E, M> Strict<E, M> {
+    /// Consumes the `Strict` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T, M> aide::OperationInput for Strict<T, M>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+impl<E: HasValidate, M> HasValidate for Strict<E, M> {
+    type Validate = E::Validate;
+    fn get_validate(&self) -> &Self::Validate {
+        self.0.get_validate()
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<'v, E: HasValidateArgs<'v>, M> HasValidateArgs<'v> for Strict<E, M> {
+    type ValidateArgs = E::ValidateArgs;
+    fn get_validate_args(&self) -> &Self::ValidateArgs {
+        self.0.get_validate_args()
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<E: crate::HasModify, M> crate::HasModify for Strict<E, M> {
+    type Modify = E::Modify;
+    fn get_modify(&mut self) -> &mut Self::Modify {
+        self.0.get_modify()
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<E: crate::PayloadExtractor, M> crate::PayloadExtractor for Strict<E, M> {
+    type Payload = E::Payload;
+
+    fn get_payload(self) -> Self::Payload {
+        self.0.get_payload()
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<E: crate::HasValidify, M> crate::HasValidify for Strict<E, M> {
+    type Validify = E::Validify;
+    type PayloadExtractor = Strict<E::PayloadExtractor, M>;
+
+    fn from_validify(v: Self::Validify) -> Self {
+        Strict(E::from_validify(v), PhantomData)
+    }
+}
+
+/// Fields ignored while deserializing the data.
+///
+/// Serialized as a map from the field paths to [`UNKNOWN_FIELD`], e.g. `{"items[1].nmae":["unknown_field"]}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownFields(pub Vec<String>);
+
+impl UnknownFields {
+    /// Collects the fields ignored while deserializing `T`, and the fields buffered by a `#[serde(flatten)]`
+    /// field that none of the flattened fields consumes.
+    ///
+    /// `deserializer` returns a deserializer of the same data on each call, the data is deserialized once, and once
+    /// more for each buffered field, up to [`MAX_PROBES`] times. Buffered fields beyond the first [`MAX_PROBES`] are
+    /// not checked. The result of the deserialization is discarded, the error (if any) is returned by the inner
+    /// extractor.
+    pub fn collect<'de, T, D>(deserializer: impl Fn() -> D) -> Self
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let trace = RefCell::new(Trace::default());
+        let _ = T::deserialize(Traced::new(deserializer(), &trace));
+        let Trace {
+            mut ignored,
+            buffered,
+            ..
+        } = trace.into_inner();
+        for path in buffered.into_iter().take(MAX_PROBES) {
+            // The field is unknown if `T` still deserializes without its value
+            let trace = RefCell::new(Trace::probe(path.clone()));
+            if T::deserialize(Traced::new(deserializer(), &trace)).is_ok() {
+                ignored.push(path);
+            }
+        }
+        Self(ignored)
+    }
+
+    /// Returns `true` if no field was ignored.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[cfg(any(feature = "json", feature = "query", feature = "form"))]
+    fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Display for UnknownFields {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown fields: {}", self.0.join(", "))
+    }
+}
+
+impl std::error::Error for UnknownFields {}
+
+impl Serialize for UnknownFields {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for field in &self.0 {
+            map.serialize_entry(field, &[UNKNOWN_FIELD])?;
+        }
+        map.end()
+    }
+}
+
+/// `StrictRejection` is returned when the `Strict` extractor fails.
+///
+pub type StrictRejection<E> = ValidationRejection<UnknownFields, E>;
+
+/// Buffers the body of `req`, returning it along with a request carrying the same body.
+#[cfg(any(feature = "json", feature = "form"))]
+async fn buffer<State: Send + Sync>(
+    req: Request,
+    state: &State,
+) -> Result<(axum::body::Bytes, Request), axum::extract::rejection::BytesRejection> {
+    let (parts, body) = req.into_parts();
+    let bytes =
+        axum::body::Bytes::from_request(Request::from_parts(parts.clone(), body), state).await?;
+    let req = Request::from_parts(parts, axum::body::Body::from(bytes.clone()));
+    Ok((bytes, req))
+}
+
+/// Collects the fields ignored while deserializing `T` from a `key=value` query string or form body.
+#[cfg(any(feature = "query", feature = "form"))]
+fn unknown_pairs<T: DeserializeOwned>(input: &[u8]) -> UnknownFields {
+    UnknownFields::collect::<T, _>(|| {
+        serde_urlencoded::Deserializer::new(form_urlencoded::parse(input))
+    })
+}
+
+#[cfg(feature = "json")]
+impl<State, T, M> FromRequest<State> for Strict<axum::Json<T>, M>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+    M: StrictMode<State>,
+{
+    type Rejection = StrictRejection<axum::extract::rejection::JsonRejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        if !M::is_strict(state) {
+            let json = axum::Json::<T>::from_request(req, state)
+                .await
+                .map_err(StrictRejection::Inner)?;
+            return Ok(Strict(json, PhantomData));
+        }
+        let (bytes, req) = buffer(req, state)
+            .await
+            .map_err(|rejection| StrictRejection::Inner(rejection.into()))?;
+        let json = axum::Json::<T>::from_request(req, state)
+            .await
+            .map_err(StrictRejection::Inner)?;
+        // The body is valid JSON, as `Json` accepted it
+        let value = serde_json::from_slice::<serde_json::Value>(&bytes).unwrap_or_default();
+        UnknownFields::collect::<T, _>(|| &value)
+            .into_result()
+            .map_err(StrictRejection::Valid)?;
+        Ok(Strict(json, PhantomData))
+    }
+}
+
+#[cfg(feature = "query")]
+impl<State, T, M> FromRequestParts<State> for Strict<axum::extract::Query<T>, M>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+    M: StrictMode<State>,
+{
+    type Rejection = StrictRejection<axum::extract::rejection::QueryRejection>;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &State,
+    ) -> Result<Self, Self::Rejection> {
+        let query = axum::extract::Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(StrictRejection::Inner)?;
+        if M::is_strict(state) {
+            unknown_pairs::<T>(parts.uri.query().unwrap_or_default().as_bytes())
+                .into_result()
+                .map_err(StrictRejection::Valid)?;
+        }
+        Ok(Strict(query, PhantomData))
+    }
+}
+
+#[cfg(feature = "form")]
+impl<State, T, M> FromRequest<State> for Strict<axum::Form<T>, M>
+where
+    State: Send + Sync,
+    T: DeserializeOwned,
+    M: StrictMode<State>,
+{
+    type Rejection = StrictRejection<axum::extract::rejection::FormRejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        if !M::is_strict(state) {
+            let form = axum::Form::<T>::from_request(req, state)
+                .await
+                .map_err(StrictRejection::Inner)?;
+            return Ok(Strict(form, PhantomData));
+        }
+        // Like `Form`, read the query string of `GET` and `HEAD` requests
+        let query = (req.method() == axum::http::Method::GET
+            || req.method() == axum::http::Method::HEAD)
+            .then(|| req.uri().query().unwrap_or_default().to_owned());
+        let (bytes, req) = buffer(req, state)
+            .await
+            .map_err(|rejection| StrictRejection::Inner(rejection.into()))?;
+        let form = axum::Form::<T>::from_request(req, state)
+            .await
+            .map_err(StrictRejection::Inner)?;
+        match query {
+            Some(query) => unknown_pairs::<T>(query.as_bytes()),
+            None => unknown_pairs::<T>(&bytes),
+        }
+        .into_result()
+        .map_err(StrictRejection::Valid)?;
+        Ok(Strict(form, PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::io;
+
+    const STRICT: &str = "strict";
+
+    fn collect<T: for<'de> Deserialize<'de>>(json: &str) -> anyhow::Result<UnknownFields> {
+        let value = serde_json::from_str::<serde_json::Value>(json)?;
+        Ok(UnknownFields::collect::<T, _>(|| &value))
+    }
+
+    #[test]
+    fn strict_deref_deref_mut_into_inner() {
+        let mut inner = String::from(STRICT);
+        let mut v = Strict::<_>(inner.clone(), PhantomData);
+        assert_eq!(&inner, v.deref());
+        inner.push_str(STRICT);
+        v.deref_mut().push_str(STRICT);
+        assert_eq!(&inner, v.deref());
+        println!("{}", v);
+        assert_eq!(inner, v.into_inner());
+    }
+
+    #[test]
+    fn unknown_fields() -> anyhow::Result<()> {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Item {
+            name: String,
+        }
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Order {
+            items: Vec<Item>,
+            note: Option<Item>,
+            #[serde(flatten)]
+            extra: std::collections::HashMap<String, String>,
+        }
+
+        let json =
+            r#"{"items":[{"name":"a"},{"name":"b","nmae":"c"}],"note":{"name":"d","x":1},"y":"z"}"#;
+        assert_eq!(collect::<Order>(json)?.0, ["items[1].nmae", "note.x"]);
+
+        let unknown = collect::<Item>(r#"{"name":"a","emial":"b"}"#)?;
+        assert_eq!(unknown.to_string(), "unknown fields: emial");
+        assert_eq!(
+            serde_json::to_value(&unknown)?,
+            serde_json::json!({ "emial": [UNKNOWN_FIELD] })
+        );
+        assert!(collect::<Item>(r#"{"name":"a"}"#)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_flattened_fields() -> anyhow::Result<()> {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Inner {
+            email: String,
+            #[serde(default)]
+            newsletter: bool,
+        }
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Outer {
+            name: String,
+            #[serde(flatten)]
+            inner: Inner,
+        }
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Team {
+            members: Vec<Outer>,
+            #[serde(flatten)]
+            lead: Outer,
+        }
+
+        assert_eq!(
+            collect::<Outer>(r#"{"name":"a","email":"b","emial":"c"}"#)?.0,
+            ["emial"]
+        );
+        assert!(collect::<Outer>(r#"{"name":"a","email":"b","newsletter":true}"#)?.is_empty());
+        assert_eq!(
+            collect::<Team>(
+                r#"{"members":[{"name":"a","email":"b","x":1}],"name":"c","email":"d","y":[2]}"#
+            )?
+            .0,
+            ["members[0].x", "y"]
+        );
+
+        // Keys are probed in order, the unknown `a{i}` ones before the known `email`
+        let json = (0..10_000)
+            .map(|i| format!(r#""a{i}":{i}"#))
+            .collect::<Vec<_>>()
+            .join(",");
+        let unknown = collect::<Outer>(&format!(r#"{{"name":"a","email":"b",{json}}}"#))?;
+        assert_eq!(unknown.0.len(), MAX_PROBES);
+
+        let query = b"name=a&email=b&emial=c";
+        let unknown = UnknownFields::collect::<Outer, _>(|| {
+            serde_urlencoded::Deserializer::new(form_urlencoded::parse(query))
+        });
+        assert_eq!(unknown.0, ["emial"]);
+        Ok(())
+    }
+
+    #[cfg(feature = "query")]
+    #[tokio::test]
+    async fn strict_query() {
+        use axum::extract::{Query, Request};
+
+        #[derive(Debug, Deserialize)]
+        struct Page {
+            page: u32,
+        }
+
+        async fn extract<M: StrictMode<StrictConfig>>(
+            uri: &'static str,
+            strict: bool,
+        ) -> Result<u32, StrictRejection<axum::extract::rejection::QueryRejection>> {
+            let (mut parts, _) = Request::builder()
+                .uri(uri)
+                .body(())
+                .expect("build request")
+                .into_parts();
+            Strict::<Query<Page>, M>::from_request_parts(
+                &mut parts,
+                &StrictConfig { enabled: strict },
+            )
+            .await
+            .map(|Strict(Query(page), _)| page.page)
+        }
+
+        assert!(matches!(
+            extract::<StrictAlways>("/?page=1", false).await,
+            Ok(1)
+        ));
+        assert!(matches!(
+            extract::<StrictAlways>("/?page=1&size=10", false).await,
+            Err(StrictRejection::Valid(UnknownFields(fields))) if fields == ["size"]
+        ));
+        assert!(matches!(
+            extract::<StrictFromState>("/?page=1&size=10", false).await,
+            Ok(1)
+        ));
+        assert!(matches!(
+            extract::<StrictFromState>("/?page=1&size=10", true).await,
+            Err(StrictRejection::Valid(_))
+        ));
+        assert!(matches!(
+            extract::<StrictAlways>("/?size=10", true).await,
+            Err(StrictRejection::Inner(_))
+        ));
+    }
+
+    #[cfg(all(feature = "json", feature = "form"))]
+    #[tokio::test]
+    async fn strict_json_form() -> anyhow::Result<()> {
+        use crate::VALIDATION_ERROR_STATUS;
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use axum::{Form, Json, Router};
+        use reqwest::Client;
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener;
+
+        #[derive(Deserialize)]
+        struct User {
+            email: String,
+            #[serde(default)]
+            newsletter: bool,
+        }
+
+        async fn json(Strict(Json(user), _): Strict<Json<User>>) -> String {
+            format!("{} {}", user.email, user.newsletter)
+        }
+
+        async fn form(Strict(Form(user), _): Strict<Form<User>, StrictFromState>) -> String {
+            format!("{} {}", user.email, user.newsletter)
+        }
+
+        let router = Router::new()
+            .route("/json", post(json))
+            .route("/form", post(form).get(form))
+            .with_state(StrictConfig { enabled: true });
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let client = Client::new();
+        let response = client
+            .post(format!("{url}/json"))
+            .json(&serde_json::json!({ "email": "a@b.c", "newsletter": true }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "a@b.c true");
+
+        let response = client
+            .post(format!("{url}/json"))
+            .json(&serde_json::json!({ "email": "a@b.c", "newslettre": true }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        #[cfg(not(feature = "into_json"))]
+        assert_eq!(response.text().await?, "unknown fields: newslettre");
+        #[cfg(feature = "into_json")]
+        assert_eq!(
+            response.json::<serde_json::Value>().await?,
+            serde_json::json!({ "newslettre": [UNKNOWN_FIELD] })
+        );
+
+        let response = client
+            .post(format!("{url}/json"))
+            .json(&serde_json::json!({ "emial": "a@b.c" }))
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = client
+            .post(format!("{url}/form"))
+            .form(&[("email", "a@b.c")])
+            .send()
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await?, "a@b.c false");
+
+        let response = client
+            .post(format!("{url}/form"))
+            .form(&[("email", "a@b.c"), ("news", "true")])
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+
+        let response = client
+            .get(format!("{url}/form?email=a@b.c&news=true"))
+            .send()
+            .await?;
+        assert_eq!(response.status(), VALIDATION_ERROR_STATUS);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "validify", feature = "json"))]
+    #[tokio::test]
+    async fn strict_validify() -> anyhow::Result<()> {
+        use crate::{Modified, Validified, ValidifiedByRef};
+        use axum::extract::Request;
+        use axum::http::header::CONTENT_TYPE;
+        use axum::response::IntoResponse;
+        use axum::Json;
+        use validify::{Payload, Validify};
+
+        #[derive(Debug, Deserialize, Validify, Payload)]
+        struct User {
+            #[modify(trim)]
+            #[validate(length(min = 1))]
+            name: String,
+        }
+
+        fn request(body: &'static str) -> Request {
+            Request::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(body))
+                .expect("build request")
+        }
+
+        let Validified(Strict(Json(user), _)) =
+            Validified::<Strict<Json<User>>>::from_request(request(r#"{"name":" a "}"#), &())
+                .await
+                .map_err(|rejection| anyhow::anyhow!("{rejection}"))?;
+        assert_eq!(user.name, "a");
+        let rejection = Validified::<Strict<Json<User>>>::from_request(
+            request(r#"{"name":"a","nmae":"b"}"#),
+            &(),
+        )
+        .await
+        .expect_err("unknown field");
+        assert_eq!(
+            rejection.into_response().status(),
+            crate::VALIDATION_ERROR_STATUS
+        );
+
+        let Modified(Strict(Json(user), _)) =
+            Modified::<Strict<Json<User>>>::from_request(request(r#"{"name":" b "}"#), &())
+                .await
+                .map_err(|rejection| anyhow::anyhow!("{rejection}"))?;
+        assert_eq!(user.name, "b");
+        assert!(ValidifiedByRef::<Strict<Json<User>>>::from_request(
+            request(r#"{"name":"  "}"#),
+            &()
+        )
+        .await
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn display_error() {
+        // StrictRejection::Valid Display
+        let fields = UnknownFields(vec![String::from(STRICT)]);
+        let vr = StrictRejection::<String>::Valid(fields.clone());
+        assert_eq!(vr.to_string(), fields.to_string());
+
+        // StrictRejection::Inner Display
+        let inner = String::from(STRICT);
+        let vr = StrictRejection::<String>::Inner(inner.clone());
+        assert_eq!(inner.to_string(), vr.to_string());
+
+        // StrictRejection::Valid Error
+        let vr = StrictRejection::<io::Error>::Valid(fields);
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<UnknownFields>().is_some())
+        );
+
+        // StrictRejection::Inner Error
+        let vr = StrictRejection::<io::Error>::Inner(io::Error::other(STRICT));
+        assert!(
+            matches!(vr.source(), Some(source) if source.downcast_ref::<io::Error>().is_some())
+        );
+    }
+}
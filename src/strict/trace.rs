@@ -0,0 +1,506 @@
+//! Deserializer wrapper tracing the keys ignored while deserializing, and the keys whose value is
+//! deserialized without a type hint (`deserialize_any`), as `#[serde(flatten)]` fields buffer them.
+//!
+//! Serde doesn't report buffered keys that none of the flattened fields consumes, so each of them is
+//! probed: the data is deserialized again with the value of the key replaced by a marker no typed
+//! field accepts. If it still succeeds, the key is unknown. Only the public `Deserializer` API is
+//! relied on, any key deserialized without a type hint is probed.
+
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, IgnoredAny, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use std::cell::RefCell;
+use std::fmt::Formatter;
+
+/// Keys found while deserializing, by path.
+#[derive(Debug, Default)]
+pub(super) struct Trace {
+    /// Keys ignored by the deserialized type
+    pub(super) ignored: Vec<String>,
+    /// Keys whose value was deserialized without a type hint, and possibly buffered
+    pub(super) buffered: Vec<String>,
+    /// Key whose value is replaced by the marker
+    pub(super) marker: Option<String>,
+    /// Last key read by a map
+    key: Option<String>,
+}
+
+impl Trace {
+    /// Creates a trace replacing the value of the key at `path` by the marker.
+    pub(super) fn probe(path: String) -> Self {
+        Self {
+            marker: Some(path),
+            ..Self::default()
+        }
+    }
+}
+
+/// Writes `key` under `parent`, as `a.b[1].c`.
+fn join(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+/// Position of a deserializer in its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// Key of a map entry
+    Key,
+    /// Value of a map entry
+    Value,
+    /// Anything else, such as the root or a sequence element
+    Other,
+}
+
+/// Wraps a deserializer, and every nested one, to record the keys in the trace.
+pub(super) struct Traced<'a, D> {
+    de: D,
+    path: String,
+    trace: &'a RefCell<Trace>,
+    position: Position,
+}
+
+impl<'a, D> Traced<'a, D> {
+    pub(super) fn new(de: D, trace: &'a RefCell<Trace>) -> Self {
+        Self::at(de, String::new(), trace)
+    }
+
+    fn at(de: D, path: String, trace: &'a RefCell<Trace>) -> Self {
+        Self {
+            de,
+            path,
+            trace,
+            position: Position::Other,
+        }
+    }
+
+    fn wrap<V>(&self, delegate: V) -> Wrap<'a, V> {
+        Wrap {
+            delegate,
+            path: self.path.clone(),
+            trace: self.trace,
+            key: self.position == Position::Key,
+        }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error> {
+                let visitor = self.wrap(visitor);
+                self.de.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Traced<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        if self.position == Position::Value {
+            self.trace.borrow_mut().buffered.push(self.path.clone());
+        }
+        let visitor = self.wrap(visitor);
+        self.de.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        if self.position != Position::Key {
+            self.trace.borrow_mut().ignored.push(self.path);
+        }
+        self.de.deserialize_ignored_any(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.de.is_human_readable()
+    }
+}
+
+/// Wraps a visitor, to wrap the nested deserializers, and to capture keys.
+struct Wrap<'a, V> {
+    delegate: V,
+    path: String,
+    trace: &'a RefCell<Trace>,
+    key: bool,
+}
+
+impl<V> Wrap<'_, V> {
+    fn capture(&self, key: impl ToString) {
+        if self.key {
+            self.trace.borrow_mut().key = Some(key.to_string());
+        }
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty);)*) => {
+        $(
+            fn $method<E: Error>(self, v: $ty) -> Result<V::Value, E> {
+                self.capture(v);
+                self.delegate.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Wrap<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        self.delegate.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_i128(i128);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_u128(u128);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_str(&str);
+        visit_borrowed_str(&'de str);
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<V::Value, E> {
+        self.capture(&v);
+        self.delegate.visit_string(v)
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<V::Value, E> {
+        self.capture(String::from_utf8_lossy(v));
+        self.delegate.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, v: &'de [u8]) -> Result<V::Value, E> {
+        self.capture(String::from_utf8_lossy(v));
+        self.delegate.visit_borrowed_bytes(v)
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<V::Value, E> {
+        self.capture(String::from_utf8_lossy(&v));
+        self.delegate.visit_byte_buf(v)
+    }
+
+    fn visit_none<E: Error>(self) -> Result<V::Value, E> {
+        self.delegate.visit_none()
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<V::Value, E> {
+        self.delegate.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        self.delegate
+            .visit_some(Traced::at(deserializer, self.path, self.trace))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        self.delegate
+            .visit_newtype_struct(Traced::at(deserializer, self.path, self.trace))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.delegate.visit_seq(Seq {
+            seq,
+            path: self.path,
+            trace: self.trace,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        self.delegate.visit_map(Map {
+            map,
+            path: self.path,
+            trace: self.trace,
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        self.delegate.visit_enum(Enum {
+            data,
+            path: self.path,
+            trace: self.trace,
+        })
+    }
+}
+
+/// Wraps a seed, to wrap its deserializer.
+struct Seed<'a, S> {
+    seed: S,
+    path: String,
+    trace: &'a RefCell<Trace>,
+    position: Position,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Seed<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        let mut traced = Traced::at(deserializer, self.path, self.trace);
+        traced.position = self.position;
+        self.seed.deserialize(traced)
+    }
+}
+
+/// Seed skipping the value, and deserializing the marker instead.
+struct MarkerSeed<S>(S);
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for MarkerSeed<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        deserializer.deserialize_ignored_any(IgnoredAny)?;
+        self.0.deserialize(Marker(std::marker::PhantomData))
+    }
+}
+
+/// Deserializer of bytes that are not valid UTF-8, which only buffering and untyped fields accept.
+struct Marker<E>(std::marker::PhantomData<E>);
+
+impl<'de, E: Error> Deserializer<'de> for Marker<E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_bytes(&[0xff])
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct Seq<'a, A> {
+    seq: A,
+    path: String,
+    trace: &'a RefCell<Trace>,
+    index: usize,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for Seq<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, A::Error> {
+        let path = format!("{}[{}]", self.path, self.index);
+        self.index += 1;
+        self.seq.next_element_seed(Seed {
+            seed,
+            path,
+            trace: self.trace,
+            position: Position::Other,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.seq.size_hint()
+    }
+}
+
+struct Map<'a, A> {
+    map: A,
+    path: String,
+    trace: &'a RefCell<Trace>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for Map<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        self.map.next_key_seed(Seed {
+            seed,
+            path: self.path.clone(),
+            trace: self.trace,
+            position: Position::Key,
+        })
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, A::Error> {
+        let key = self.trace.borrow_mut().key.take().unwrap_or_default();
+        let path = join(&self.path, &key);
+        if self.trace.borrow().marker.as_ref() == Some(&path) {
+            return self.map.next_value_seed(MarkerSeed(seed));
+        }
+        self.map.next_value_seed(Seed {
+            seed,
+            path,
+            trace: self.trace,
+            position: Position::Value,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+struct Enum<'a, A> {
+    data: A,
+    path: String,
+    trace: &'a RefCell<Trace>,
+}
+
+impl<'a, 'de, A: EnumAccess<'de>> EnumAccess<'de> for Enum<'a, A> {
+    type Error = A::Error;
+    type Variant = Variant<'a, A::Variant>;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Self::Variant), A::Error> {
+        let (value, variant) = self.data.variant_seed(seed)?;
+        Ok((
+            value,
+            Variant {
+                variant,
+                path: self.path,
+                trace: self.trace,
+            },
+        ))
+    }
+}
+
+struct Variant<'a, A> {
+    variant: A,
+    path: String,
+    trace: &'a RefCell<Trace>,
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for Variant<'_, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.variant.unit_variant()
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value, A::Error> {
+        self.variant.newtype_variant_seed(Seed {
+            seed,
+            path: self.path,
+            trace: self.trace,
+            position: Position::Other,
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.variant.tuple_variant(
+            len,
+            Wrap {
+                delegate: visitor,
+                path: self.path,
+                trace: self.trace,
+                key: false,
+            },
+        )
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        self.variant.struct_variant(
+            fields,
+            Wrap {
+                delegate: visitor,
+                path: self.path,
+                trace: self.trace,
+                key: false,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn buffered_keys() -> anyhow::Result<()> {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Inner {
+            email: String,
+        }
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Outer {
+            name: String,
+            items: Vec<u32>,
+            #[serde(flatten)]
+            inner: Inner,
+        }
+
+        let value = serde_json::json!({ "name": "a", "items": [1], "email": "b", "emial": "c" });
+        let trace = RefCell::new(Trace::default());
+        Outer::deserialize(Traced::new(&value, &trace))?;
+        let trace = trace.into_inner();
+        assert_eq!(trace.buffered, ["email", "emial"]);
+        assert!(trace.ignored.is_empty());
+
+        // The marker replaces the value of the probed key, which only a buffering field accepts
+        let trace = RefCell::new(Trace::probe(String::from("email")));
+        assert!(Outer::deserialize(Traced::new(&value, &trace)).is_err());
+        let trace = RefCell::new(Trace::probe(String::from("emial")));
+        assert!(Outer::deserialize(Traced::new(&value, &trace)).is_ok());
+        Ok(())
+    }
+}
@@ -154,6 +154,24 @@ impl<T: validify::Modify> crate::HasModify for TypedHeader<T> {
     }
 }
 
+#[cfg(feature = "validify")]
+impl<T> crate::PayloadExtractor for TypedHeader<T> {
+    type Payload = T;
+
+    fn get_payload(self) -> Self::Payload {
+        self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Validify + validify::ValidifyPayload> crate::HasValidify for TypedHeader<T> {
+    type Validify = T;
+    type PayloadExtractor = TypedHeader<T::Payload>;
+    fn from_validify(v: Self::Validify) -> Self {
+        TypedHeader(v)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{ValidTest, ValidTestParameter};
@@ -9,6 +9,10 @@
 //! 1. Implement `TryFromMultipart` and `Validate` for your data type `T`.
 //! 2. In your handler function, use `Valid<TypedMultipart<T>>` or `Valid<BaseMultipart<T, E>` as some parameter's type.
 //!
+//! With `validify`, `Validified<TypedMultipart<T>>` constructs the data from a payload extracted as
+//! `TypedMultipart<T::Payload>`. `BaseMultipart<T, R>` can only be constructed by `axum_typed_multipart`, so it supports
+//! `Validated`, `Modified` and `ValidifiedByRef`, but not `Validified`.
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -135,6 +139,15 @@ impl<T: validify::Modify, R> crate::HasModify for BaseMultipart<T, R> {
     }
 }
 
+#[cfg(feature = "validify")]
+impl<T, R> crate::PayloadExtractor for BaseMultipart<T, R> {
+    type Payload = T;
+
+    fn get_payload(self) -> Self::Payload {
+        self.data
+    }
+}
+
 impl<T> HasValidate for TypedMultipart<T> {
     type Validate = T;
     fn get_validate(&self) -> &T {
@@ -159,6 +172,24 @@ impl<T: validify::Modify> crate::HasModify for TypedMultipart<T> {
     }
 }
 
+#[cfg(feature = "validify")]
+impl<T> crate::PayloadExtractor for TypedMultipart<T> {
+    type Payload = T;
+
+    fn get_payload(self) -> Self::Payload {
+        self.0
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T: validify::Validify + validify::ValidifyPayload> crate::HasValidify for TypedMultipart<T> {
+    type Validify = T;
+    type PayloadExtractor = TypedMultipart<T::Payload>;
+    fn from_validify(v: Self::Validify) -> Self {
+        TypedMultipart(v)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{ValidTest, ValidTestParameter};
@@ -186,6 +217,31 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "validify")]
+    #[tokio::test]
+    async fn base_multipart_payload() -> anyhow::Result<()> {
+        use crate::PayloadExtractor;
+        use axum::extract::{FromRequest, Request};
+        use axum_typed_multipart::{TryFromMultipart, TypedMultipartError};
+
+        #[derive(TryFromMultipart)]
+        struct Upload {
+            name: String,
+        }
+
+        let body = "--b\r\ncontent-disposition: form-data; name=\"name\"\r\n\r\nfile\r\n--b--\r\n";
+        let request = Request::builder()
+            .header("content-type", "multipart/form-data; boundary=b")
+            .body(axum::body::Body::from(body))?;
+        let Ok(multipart) =
+            BaseMultipart::<Upload, TypedMultipartError>::from_request(request, &()).await
+        else {
+            anyhow::bail!("invalid multipart body");
+        };
+        assert_eq!(multipart.get_payload().name, "file");
+        Ok(())
+    }
+
     impl<T: ValidTestParameter> ValidTest for TypedMultipart<T>
     where
         Form: From<&'static T>,
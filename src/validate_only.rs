@@ -0,0 +1,346 @@
+//! # Validate-only (dry run) requests
+//!
+//! Form UIs often validate data on the server side as the user types, without triggering side effects.
+//!
+//! `ValidateOnly<E, M>` wraps a validation extractor `E` such as `Valid<Json<T>>`, `Garde<Json<T>>` or
+//! `Validated<Json<T>>`. When the request asks for validation only (as detected by the marker type `M`),
+//! the request is short-circuited after successful validation with `204 No Content`, without invoking the handler.
+//! Validation errors are returned as usual.
+//!
+//! ## Modes
+//!
+//! * [`PreferValidateOnly`] (default): the request has a `Prefer: validate-only` header.
+//! * [`ValidateOnlyQuery`]: the request has a `validate_only` query parameter, whose value is not `false` or `0`.
+//!
+//! Implement `ValidateOnlyMode` for your own marker type to use another mechanism.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::{Valid, ValidateOnly, ValidateOnlyQuery};
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new()
+//!             .route("/json", post(handler))
+//!             .route("/json_query", post(query_handler))
+//!     }
+//!
+//!     // `Prefer: validate-only` returns `204 No Content` for valid data
+//!     async fn handler(ValidateOnly(Valid(Json(parameter)), _): ValidateOnly<Valid<Json<Parameter>>>) {
+//!         // Only invoked if the request is not validate-only
+//!         assert!(parameter.validate().is_ok());
+//!     }
+//!
+//!     // `?validate_only=true` returns `204 No Content` for valid data
+//!     async fn query_handler(
+//!         ValidateOnly(Valid(Json(parameter)), _): ValidateOnly<Valid<Json<Parameter>>, ValidateOnlyQuery>,
+//!     ) {
+//!         assert!(parameter.validate().is_ok());
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[validate(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderName, StatusCode};
+use axum::response::{IntoResponse, Response};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// The `Prefer` header, see RFC 7240.
+const PREFER: HeaderName = HeaderName::from_static("prefer");
+
+/// Trait for marker types that detect validate-only requests.
+///
+pub trait ValidateOnlyMode {
+    /// Returns `true` if the request asks for validation only
+    fn is_validate_only(parts: &Parts) -> bool;
+}
+
+/// Detects validate-only requests by the `Prefer: validate-only` header.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferValidateOnly;
+
+impl ValidateOnlyMode for PreferValidateOnly {
+    fn is_validate_only(parts: &Parts) -> bool {
+        parts
+            .headers
+            .get_all(PREFER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|preference| preference.trim().eq_ignore_ascii_case("validate-only"))
+    }
+}
+
+/// Detects validate-only requests by the `validate_only` query parameter,
+/// whose value is not `false` or `0`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidateOnlyQuery;
+
+impl ValidateOnlyMode for ValidateOnlyQuery {
+    fn is_validate_only(parts: &Parts) -> bool {
+        parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter_map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (key == "validate_only").then_some(value)
+            })
+            .any(|value| value != "false" && value != "0")
+    }
+}
+
+/// # `ValidateOnly` data extractor
+///
+/// `ValidateOnly` short-circuits validate-only requests after the inner validation extractor succeeds,
+/// see the [module documentation](self).
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidateOnly<E, M = PreferValidateOnly>(pub E, pub PhantomData<M>);
+
+impl<E, M> Deref for ValidateOnly<E, M> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E, M> DerefMut for ValidateOnly<E, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display, M> Display for ValidateOnly<T, M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E, M> ValidateOnly<E, M> {
+    /// Consumes the `ValidateOnly` and returns the inner extractor.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T, M> aide::OperationInput for ValidateOnly<T, M>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+/// `ValidateOnlyRejection` is returned when the `ValidateOnly` extractor short-circuits the request.
+///
+#[derive(Debug)]
+pub enum ValidateOnlyRejection<E> {
+    /// The data is valid and the request asks for validation only, responds with `204 No Content`.
+    Validated,
+    /// The inner extractor failed.
+    Inner(E),
+}
+
+impl<E: Display> Display for ValidateOnlyRejection<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidateOnlyRejection::Validated => write!(f, "validated"),
+            ValidateOnlyRejection::Inner(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ValidateOnlyRejection<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ValidateOnlyRejection::Validated => None,
+            ValidateOnlyRejection::Inner(e) => Some(e),
+        }
+    }
+}
+
+impl<E: IntoResponse> IntoResponse for ValidateOnlyRejection<E> {
+    fn into_response(self) -> Response {
+        match self {
+            ValidateOnlyRejection::Validated => StatusCode::NO_CONTENT.into_response(),
+            ValidateOnlyRejection::Inner(e) => e.into_response(),
+        }
+    }
+}
+
+impl<State, Extractor, Mode> FromRequest<State> for ValidateOnly<Extractor, Mode>
+where
+    State: Send + Sync,
+    Extractor: FromRequest<State>,
+    Mode: ValidateOnlyMode,
+{
+    type Rejection = ValidateOnlyRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let (parts, body) = req.into_parts();
+        let validate_only = Mode::is_validate_only(&parts);
+        let inner = Extractor::from_request(Request::from_parts(parts, body), state)
+            .await
+            .map_err(ValidateOnlyRejection::Inner)?;
+        if validate_only {
+            return Err(ValidateOnlyRejection::Validated);
+        }
+        Ok(ValidateOnly(inner, PhantomData))
+    }
+}
+
+impl<State, Extractor, Mode> FromRequestParts<State> for ValidateOnly<Extractor, Mode>
+where
+    State: Send + Sync,
+    Extractor: FromRequestParts<State>,
+    Mode: ValidateOnlyMode,
+{
+    type Rejection = ValidateOnlyRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let validate_only = Mode::is_validate_only(parts);
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(ValidateOnlyRejection::Inner)?;
+        if validate_only {
+            return Err(ValidateOnlyRejection::Validated);
+        }
+        Ok(ValidateOnly(inner, PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn parts(uri: &str, prefer: Option<&str>) -> Parts {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(prefer) = prefer {
+            builder = builder.header(PREFER, prefer);
+        }
+        builder
+            .body(Body::empty())
+            .expect("build request")
+            .into_parts()
+            .0
+    }
+
+    #[test]
+    fn validate_only_modes() {
+        assert!(PreferValidateOnly::is_validate_only(&parts(
+            "/",
+            Some("validate-only")
+        )));
+        assert!(PreferValidateOnly::is_validate_only(&parts(
+            "/",
+            Some("return=minimal, Validate-Only")
+        )));
+        assert!(!PreferValidateOnly::is_validate_only(&parts(
+            "/",
+            Some("return=minimal")
+        )));
+        assert!(!PreferValidateOnly::is_validate_only(&parts("/", None)));
+
+        assert!(ValidateOnlyQuery::is_validate_only(&parts(
+            "/?validate_only",
+            None
+        )));
+        assert!(ValidateOnlyQuery::is_validate_only(&parts(
+            "/?a=1&validate_only=true",
+            None
+        )));
+        assert!(!ValidateOnlyQuery::is_validate_only(&parts(
+            "/?validate_only=false",
+            None
+        )));
+        assert!(!ValidateOnlyQuery::is_validate_only(&parts("/?a=1", None)));
+        assert!(!ValidateOnlyQuery::is_validate_only(&parts("/", None)));
+    }
+
+    #[cfg(all(feature = "validator", feature = "query"))]
+    #[tokio::test]
+    async fn validate_only() {
+        use crate::{Valid, ValidRejection};
+        use axum::extract::Query;
+        use validator::Validate;
+
+        #[derive(Debug, serde::Deserialize, Validate)]
+        struct Data {
+            #[validate(range(min = 5, max = 10))]
+            v0: i32,
+        }
+
+        type Extractor = ValidateOnly<Valid<Query<Data>>, ValidateOnlyQuery>;
+
+        let mut valid = parts("/?v0=5", None);
+        let data = Extractor::from_request_parts(&mut valid, &())
+            .await
+            .expect("valid");
+        assert_eq!(data.v0, 5);
+
+        let mut validate_only = parts("/?v0=5&validate_only=1", None);
+        let rejection = Extractor::from_request_parts(&mut validate_only, &())
+            .await
+            .expect_err("validate only");
+        assert!(matches!(rejection, ValidateOnlyRejection::Validated));
+        assert_eq!(rejection.into_response().status(), StatusCode::NO_CONTENT);
+
+        let mut invalid = parts("/?v0=11&validate_only=1", None);
+        assert!(matches!(
+            Extractor::from_request_parts(&mut invalid, &()).await,
+            Err(ValidateOnlyRejection::Inner(ValidRejection::Valid(_)))
+        ));
+
+        let request = Request::builder()
+            .uri("/?v0=5")
+            .header(PREFER, "validate-only")
+            .body(Body::empty())
+            .expect("build request");
+        assert!(matches!(
+            ValidateOnly::<Valid<Query<Data>>>::from_request(request, &()).await,
+            Err(ValidateOnlyRejection::Validated)
+        ));
+    }
+}
@@ -2,16 +2,17 @@
 //!
 //! ## Feature
 //!
-//! Enable the `validator` feature (enabled by default) to use `Valid<E>` and `ValidEx<E, A>`.
+//! Enable the `validator` feature (enabled by default) to use `Valid<E>`, `ValidEx<E>` and `ValidGroup<E, G>`.
 //!
 
 #[cfg(test)]
 pub mod test;
 
-use crate::{HasValidate, ValidationRejection};
+use crate::{HasValidate, ValidationGroup, ValidationRejection};
 use axum::extract::{FromRef, FromRequest, FromRequestParts, Request};
 use axum::http::request::Parts;
 use std::fmt::Display;
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 use validator::{Validate, ValidateArgs, ValidationErrors};
 
@@ -128,7 +129,115 @@ where
     }
 }
 
-/// `ValidRejection` is returned when the `Valid` or `ValidEx` extractor fails.
+/// # `ValidGroup` data extractor
+///
+/// `ValidGroup` validates the inner data with the rules of a validation group (scenario),
+/// selected by the marker type `G`.
+///
+/// Like `ValidEx`, the inner data type implements `ValidateArgs`. Its arguments are not taken from
+/// the state but built by the group marker type, see [`ValidationGroup`].
+///
+/// ```no_run
+/// use axum::Json;
+/// use axum_valid::{ValidGroup, ValidationGroup};
+/// use serde::Deserialize;
+/// use validator::{Validate, ValidationError};
+///
+/// #[derive(PartialEq)]
+/// pub enum Scenario {
+///     Create,
+///     Update,
+/// }
+///
+/// pub struct Create;
+///
+/// impl ValidationGroup for Create {
+///     type Context = Scenario;
+///     fn context() -> Self::Context {
+///         Scenario::Create
+///     }
+/// }
+///
+/// pub struct Update;
+///
+/// impl ValidationGroup for Update {
+///     type Context = Scenario;
+///     fn context() -> Self::Context {
+///         Scenario::Update
+///     }
+/// }
+///
+/// #[derive(Deserialize, Validate)]
+/// #[validate(context = Scenario, schema(function = "validate_id", use_context))]
+/// pub struct User {
+///     pub id: Option<u64>,
+///     #[validate(length(min = 1, max = 32))]
+///     pub name: String,
+/// }
+///
+/// fn validate_id(user: &User, scenario: &Scenario) -> Result<(), ValidationError> {
+///     match (scenario, user.id) {
+///         (Scenario::Create, None) | (Scenario::Update, Some(_)) => Ok(()),
+///         _ => Err(ValidationError::new("id")),
+///     }
+/// }
+///
+/// async fn create(ValidGroup(Json(user), _): ValidGroup<Json<User>, Create>) {
+///     assert!(user.id.is_none());
+/// }
+///
+/// async fn update(ValidGroup(Json(user), _): ValidGroup<Json<User>, Update>) {
+///     assert!(user.id.is_some());
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidGroup<E, G>(pub E, pub PhantomData<G>);
+
+impl<E, G> Deref for ValidGroup<E, G> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E, G> DerefMut for ValidGroup<E, G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Display, G> Display for ValidGroup<T, G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E, G> ValidGroup<E, G> {
+    /// Consumes the `ValidGroup` and returns the validated data within.
+    ///
+    /// This returns the `E` type which represents the data that has been
+    /// successfully validated.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T, G> aide::OperationInput for ValidGroup<T, G>
+where
+    T: aide::OperationInput,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+/// `ValidRejection` is returned when the `Valid`, `ValidEx` or `ValidGroup` extractor fails.
 ///
 pub type ValidRejection<E> = ValidationRejection<ValidationErrors, E>;
 
@@ -222,6 +331,48 @@ where
     }
 }
 
+impl<State, Extractor, Group> FromRequest<State> for ValidGroup<Extractor, Group>
+where
+    State: Send + Sync,
+    Group: ValidationGroup,
+    Extractor: for<'v> HasValidateArgs<'v> + FromRequest<State>,
+    for<'v> <Extractor as HasValidateArgs<'v>>::ValidateArgs:
+        ValidateArgs<'v, Args = &'v Group::Context>,
+{
+    type Rejection = ValidRejection<<Extractor as FromRequest<State>>::Rejection>;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request(req, state)
+            .await
+            .map_err(ValidRejection::Inner)?;
+        inner
+            .get_validate_args()
+            .validate_with_args(&Group::context())?;
+        Ok(ValidGroup(inner, PhantomData))
+    }
+}
+
+impl<State, Extractor, Group> FromRequestParts<State> for ValidGroup<Extractor, Group>
+where
+    State: Send + Sync,
+    Group: ValidationGroup,
+    Extractor: for<'v> HasValidateArgs<'v> + FromRequestParts<State>,
+    for<'v> <Extractor as HasValidateArgs<'v>>::ValidateArgs:
+        ValidateArgs<'v, Args = &'v Group::Context>,
+{
+    type Rejection = ValidRejection<<Extractor as FromRequestParts<State>>::Rejection>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        let inner = Extractor::from_request_parts(parts, state)
+            .await
+            .map_err(ValidRejection::Inner)?;
+        inner
+            .get_validate_args()
+            .validate_with_args(&Group::context())?;
+        Ok(ValidGroup(inner, PhantomData))
+    }
+}
+
 /// basic tests for validator
 #[cfg(test)]
 pub mod tests {
@@ -285,6 +436,88 @@ pub mod tests {
         assert_eq!(ve.v, v);
     }
 
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn valid_group() {
+        use axum::body::Body;
+        use axum::http::header::CONTENT_TYPE;
+        use axum::Json;
+
+        #[derive(Debug, PartialEq)]
+        enum Scenario {
+            Create,
+            Update,
+        }
+
+        struct Create;
+
+        impl ValidationGroup for Create {
+            type Context = Scenario;
+            fn context() -> Self::Context {
+                Scenario::Create
+            }
+        }
+
+        struct Update;
+
+        impl ValidationGroup for Update {
+            type Context = Scenario;
+            fn context() -> Self::Context {
+                Scenario::Update
+            }
+        }
+
+        fn validate_id(data: &Data, scenario: &Scenario) -> Result<(), ValidationError> {
+            match (scenario, data.id) {
+                (Scenario::Create, None) | (Scenario::Update, Some(_)) => Ok(()),
+                _ => Err(ValidationError::new("id")),
+            }
+        }
+
+        #[derive(Debug, serde::Deserialize, Validate)]
+        #[validate(context = Scenario, schema(function = "validate_id", use_context))]
+        struct Data {
+            id: Option<u64>,
+            #[validate(length(min = 1))]
+            name: String,
+        }
+
+        fn request(body: &'static str) -> Request {
+            Request::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .expect("build request")
+        }
+
+        let create = r#"{"name":"test"}"#;
+        let update = r#"{"id":1,"name":"test"}"#;
+
+        let ValidGroup(Json(data), _) =
+            ValidGroup::<Json<Data>, Create>::from_request(request(create), &())
+                .await
+                .expect("valid for create");
+        assert_eq!(data.id, None);
+        assert!(matches!(
+            ValidGroup::<Json<Data>, Create>::from_request(request(update), &()).await,
+            Err(ValidRejection::Valid(_))
+        ));
+
+        let ValidGroup(Json(data), _) =
+            ValidGroup::<Json<Data>, Update>::from_request(request(update), &())
+                .await
+                .expect("valid for update");
+        assert_eq!(data.id, Some(1));
+        assert!(matches!(
+            ValidGroup::<Json<Data>, Update>::from_request(request(create), &()).await,
+            Err(ValidRejection::Valid(_))
+        ));
+        assert!(matches!(
+            ValidGroup::<Json<Data>, Update>::from_request(request(r#"{"id":1,"name":""}"#), &())
+                .await,
+            Err(ValidRejection::Valid(_))
+        ));
+    }
+
     #[test]
     fn display_error() {
         // ValidRejection::Valid Display
@@ -163,10 +163,15 @@ where
 ///
 /// `Validified` provides construction, modification and validation abilities based on `validify`.
 ///
-/// It requires a serde-based inner extractor.
+/// It requires an inner extractor that can extract the payload type, such as a serde-based extractor,
+/// `TypedMultipart`, `Protobuf` or `TypedHeader`.
 ///
 /// And can treat missing fields as validation errors.
 ///
+/// The payload type generated by `validify` only implements `Deserialize`. To use the other extractors,
+/// implement `TryFromMultipart`, `prost::Message` or `Header` for the payload type,
+/// or implement `ValidifyPayload` with a payload type of your own.
+///
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Validified<E>(pub E);
 
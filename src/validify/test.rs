@@ -162,6 +162,10 @@ async fn test_main() -> anyhow::Result<()> {
             typed_header::route::TYPED_HEADER_MODIFIED,
             post(typed_header::extract_typed_header_modified),
         )
+        .route(
+            typed_header::route::TYPED_HEADER_VALIDIFIED,
+            post(typed_header::extract_typed_header_validified),
+        )
         .route(
             typed_header::route::TYPED_HEADER_VALIDIFIED_BY_REF,
             post(typed_header::extract_typed_header_validified_by_ref),
@@ -177,6 +181,10 @@ async fn test_main() -> anyhow::Result<()> {
             typed_multipart::route::TYPED_MULTIPART_MODIFIED,
             post(typed_multipart::extract_typed_multipart_modified),
         )
+        .route(
+            typed_multipart::route::TYPED_MULTIPART_VALIDIFIED,
+            post(typed_multipart::extract_typed_multipart_validified),
+        )
         .route(
             typed_multipart::route::TYPED_MULTIPART_VALIDIFIED_BY_REF,
             post(typed_multipart::extract_typed_multipart_validified_by_ref),
@@ -293,6 +301,10 @@ async fn test_main() -> anyhow::Result<()> {
             extra_protobuf::route::EXTRA_PROTOBUF_MODIFIED,
             post(extra_protobuf::extract_extra_protobuf_modified),
         )
+        .route(
+            extra_protobuf::route::EXTRA_PROTOBUF_VALIDIFIED,
+            post(extra_protobuf::extract_extra_protobuf_validified),
+        )
         .route(
             extra_protobuf::route::EXTRA_PROTOBUF_VALIDIFIED_BY_REF,
             post(extra_protobuf::extract_extra_protobuf_validified_by_ref),
@@ -603,6 +615,13 @@ async fn test_main() -> anyhow::Result<()> {
                 typed_header::route::TYPED_HEADER_MODIFIED,
             )
             .await?;
+        // Validified
+        test_executor
+            .execute_validified::<TypedHeader<ParametersValidify>>(
+                Method::POST,
+                typed_header::route::TYPED_HEADER_VALIDIFIED,
+            )
+            .await?;
         // ValidifiedByRef
         test_executor
             .execute::<TypedHeader<ParametersValidify>>(
@@ -652,6 +671,13 @@ async fn test_main() -> anyhow::Result<()> {
                 typed_multipart::route::TYPED_MULTIPART_MODIFIED,
             )
             .await?;
+        // Validified
+        test_executor
+            .execute_validified::<TypedMultipart<ParametersValidify>>(
+                Method::POST,
+                typed_multipart::route::TYPED_MULTIPART_VALIDIFIED,
+            )
+            .await?;
         // ValidifiedByRef
         test_executor
             .execute::<TypedMultipart<ParametersValidify>>(
@@ -922,6 +948,13 @@ async fn test_main() -> anyhow::Result<()> {
                 extra_protobuf::route::EXTRA_PROTOBUF_MODIFIED,
             )
             .await?;
+        // Validified
+        test_executor
+            .execute_validified::<Protobuf<ParametersValidifyWithoutPayload>>(
+                Method::POST,
+                extra_protobuf::route::EXTRA_PROTOBUF_VALIDIFIED,
+            )
+            .await?;
         // ValidifiedByRef
         test_executor
             .execute::<Protobuf<ParametersValidifyWithoutPayload>>(
@@ -1410,11 +1443,15 @@ mod typed_header {
     pub(crate) mod route {
         pub const TYPED_HEADER: &str = "/typed_header";
         pub const TYPED_HEADER_MODIFIED: &str = "/typed_header_modified";
+        pub const TYPED_HEADER_VALIDIFIED: &str = "/typed_header_validified";
         pub const TYPED_HEADER_VALIDIFIED_BY_REF: &str = "/typed_header_validified_be_ref";
     }
 
-    use super::{check_modified, check_validated, check_validified, ParametersValidify};
-    use crate::{Modified, Validated, ValidifiedByRef};
+    use super::{
+        check_modified, check_validated, check_validified, ParametersValidify,
+        ParametersValidifyPayload,
+    };
+    use crate::{Modified, Validated, Validified, ValidifiedByRef};
     use axum::http::StatusCode;
     use axum_extra::headers::{Error, Header, HeaderName, HeaderValue};
     use axum_extra::typed_header::TypedHeader;
@@ -1433,6 +1470,12 @@ mod typed_header {
         check_modified(&parameters)
     }
 
+    pub(super) async fn extract_typed_header_validified(
+        Validified(TypedHeader(parameters)): Validified<TypedHeader<ParametersValidify>>,
+    ) -> StatusCode {
+        check_validified(&parameters)
+    }
+
     pub(super) async fn extract_typed_header_validified_by_ref(
         ValidifiedByRef(TypedHeader(parameters)): ValidifiedByRef<TypedHeader<ParametersValidify>>,
     ) -> StatusCode {
@@ -1472,6 +1515,38 @@ mod typed_header {
         }
     }
 
+    impl Header for ParametersValidifyPayload {
+        fn name() -> &'static HeaderName {
+            &AXUM_VALID_PARAMETERS
+        }
+
+        fn decode<'i, I>(values: &mut I) -> Result<Self, Error>
+        where
+            Self: Sized,
+            I: Iterator<Item = &'i HeaderValue>,
+        {
+            let value = values.next().ok_or_else(Error::invalid)?;
+            let src = std::str::from_utf8(value.as_bytes()).map_err(|_| Error::invalid())?;
+            let mut split = src.split(',');
+            let v0 = split
+                .next()
+                .filter(|v0| !v0.is_empty())
+                .map(str::parse)
+                .transpose()
+                .map_err(|_| Error::invalid())?;
+            let v1 = split.next().map(String::from);
+            Ok(ParametersValidifyPayload { v0, v1 })
+        }
+
+        fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+            let v0 = self.v0.map(|v0| v0.to_string()).unwrap_or_default();
+            let v1 = self.v1.as_deref().unwrap_or_default();
+            let value =
+                HeaderValue::from_str(&format!("{v0},{v1}")).expect("Failed to build header");
+            values.extend(::std::iter::once(value));
+        }
+    }
+
     #[test]
     fn parameter_is_header() -> anyhow::Result<()> {
         let parameter = ParametersValidify {
@@ -1488,17 +1563,22 @@ mod typed_header {
 
 #[cfg(feature = "typed_multipart")]
 mod typed_multipart {
+    use super::ParametersValidifyPayload;
     use super::{
         check_modified, check_validated, check_validified, ParametersValidify,
         ParametersValidifyWithoutPayload,
     };
-    use crate::{Modified, Validated, ValidifiedByRef};
+    use crate::{Modified, Validated, Validified, ValidifiedByRef};
+    use axum::extract::Multipart;
     use axum::http::StatusCode;
-    use axum_typed_multipart::{BaseMultipart, TypedMultipart, TypedMultipartError};
+    use axum_typed_multipart::{
+        BaseMultipart, TryFromMultipart, TypedMultipart, TypedMultipartError,
+    };
 
     pub mod route {
         pub const TYPED_MULTIPART: &str = "/typed_multipart";
         pub const TYPED_MULTIPART_MODIFIED: &str = "/typed_multipart_modified";
+        pub const TYPED_MULTIPART_VALIDIFIED: &str = "/typed_multipart_validified";
         pub const TYPED_MULTIPART_VALIDIFIED_BY_REF: &str = "/typed_multipart_validified_by_ref";
 
         pub const BASE_MULTIPART: &str = "/base_multipart";
@@ -1530,6 +1610,40 @@ mod typed_multipart {
         check_modified(&parameters)
     }
 
+    // The payload generated by validify only implements `Deserialize`
+    #[axum_typed_multipart::async_trait]
+    impl TryFromMultipart for ParametersValidifyPayload {
+        async fn try_from_multipart(
+            multipart: &mut Multipart,
+        ) -> Result<Self, TypedMultipartError> {
+            let mut payload = ParametersValidifyPayload { v0: None, v1: None };
+            while let Some(field) = multipart.next_field().await? {
+                let field_name = field.name().unwrap_or_default().to_string();
+                let text = field.text().await?;
+                match field_name.as_str() {
+                    "v0" => {
+                        payload.v0 = Some(text.parse().map_err(|e: std::num::ParseIntError| {
+                            TypedMultipartError::WrongFieldType {
+                                field_name,
+                                wanted_type: String::from("i32"),
+                                source: e.into(),
+                            }
+                        })?)
+                    }
+                    "v1" => payload.v1 = Some(text),
+                    _ => return Err(TypedMultipartError::UnknownField { field_name }),
+                }
+            }
+            Ok(payload)
+        }
+    }
+
+    pub(super) async fn extract_typed_multipart_validified(
+        Validified(TypedMultipart(parameters)): Validified<TypedMultipart<ParametersValidify>>,
+    ) -> StatusCode {
+        check_validified(&parameters)
+    }
+
     pub(super) async fn extract_typed_multipart_validified_by_ref(
         ValidifiedByRef(TypedMultipart(parameters)): ValidifiedByRef<
             TypedMultipart<ParametersValidifyWithoutPayload>,
@@ -1956,13 +2070,16 @@ mod extra_protobuf {
     use super::{
         check_modified, check_validated, check_validified, ParametersValidifyWithoutPayload,
     };
-    use crate::{Modified, Validated, ValidifiedByRef};
+    use crate::{Modified, Validated, Validified, ValidifiedByRef};
     use axum::http::StatusCode;
     use axum_extra::protobuf::Protobuf;
+    use prost::Message;
+    use validify::{Validate, ValidationErrors, Validify, ValidifyPayload};
 
     pub mod route {
         pub const EXTRA_PROTOBUF: &str = "/extra_protobuf";
         pub const EXTRA_PROTOBUF_MODIFIED: &str = "/extra_protobuf_modified";
+        pub const EXTRA_PROTOBUF_VALIDIFIED: &str = "/extra_protobuf_validified";
         pub const EXTRA_PROTOBUF_VALIDIFIED_BY_REF: &str = "/extra_protobuf_validified_by_ref";
     }
 
@@ -1978,6 +2095,50 @@ mod extra_protobuf {
         check_modified(&parameters)
     }
 
+    /// The payload generated by validify only implements `Deserialize`, so use a payload of our own
+    #[derive(Clone, PartialEq, Message, Validate)]
+    pub struct ParametersValidifyProtobufPayload {
+        #[prost(int32, optional, tag = "1")]
+        #[validate(required)]
+        v0: Option<i32>,
+        #[prost(string, optional, tag = "2")]
+        #[validate(required)]
+        v1: Option<String>,
+    }
+
+    impl From<ParametersValidifyProtobufPayload> for ParametersValidifyWithoutPayload {
+        fn from(payload: ParametersValidifyProtobufPayload) -> Self {
+            ParametersValidifyWithoutPayload {
+                v0: payload.v0.unwrap_or_default(),
+                v1: payload.v1.unwrap_or_default(),
+            }
+        }
+    }
+
+    impl ValidifyPayload for ParametersValidifyWithoutPayload {
+        type Payload = ParametersValidifyProtobufPayload;
+
+        fn validate_from(payload: Self::Payload) -> Result<Self, ValidationErrors> {
+            payload.validate()?;
+            let parameters = Self::from(payload);
+            parameters.validate()?;
+            Ok(parameters)
+        }
+
+        fn validify_from(payload: Self::Payload) -> Result<Self, ValidationErrors> {
+            payload.validate()?;
+            let mut parameters = Self::from(payload);
+            parameters.validify()?;
+            Ok(parameters)
+        }
+    }
+
+    pub async fn extract_extra_protobuf_validified(
+        Validified(Protobuf(parameters)): Validified<Protobuf<ParametersValidifyWithoutPayload>>,
+    ) -> StatusCode {
+        check_validified(&parameters)
+    }
+
     pub async fn extract_extra_protobuf_validified_by_ref(
         ValidifiedByRef(Protobuf(parameters)): ValidifiedByRef<
             Protobuf<ParametersValidifyWithoutPayload>,
@@ -0,0 +1,376 @@
+//! # Non-blocking validation warnings
+//!
+//! Some rules are advisory (e.g. "password is weak"): they should not reject the request,
+//! but their violations should be surfaced to the client.
+//!
+//! ## Usage
+//!
+//! 1. Implement `Advise` for your data type `T`, checking the advisory rules.
+//! 2. In your handler function, use `Warned<E>` inside any validation extractor, e.g. `Valid<Warned<Json<T>>>`.
+//!    Hard rules still reject the request via `ValidationRejection`.
+//! 3. Optionally, return `Warnings` as a part of the response to attach the warnings as the `x-validation-warnings` header.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::routing::post;
+//!     use axum::Json;
+//!     use axum::Router;
+//!     use axum_valid::{Advise, Valid, Warned, Warnings};
+//!     use serde::Deserialize;
+//!     use validator::{Validate, ValidationError, ValidationErrors};
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/json", post(handler))
+//!     }
+//!
+//!     async fn handler(Valid(warned): Valid<Warned<Json<Parameter>>>) -> Warnings<ValidationErrors> {
+//!         let (Json(parameter), warnings) = warned.into_parts();
+//!         assert!(parameter.validate().is_ok());
+//!         warnings
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[validate(length(min = 1, max = 32))]
+//!         pub password: String,
+//!     }
+//!
+//!     impl Advise for Parameter {
+//!         type Warnings = ValidationErrors;
+//!
+//!         fn advise(&self) -> Result<(), Self::Warnings> {
+//!             let mut warnings = ValidationErrors::new();
+//!             if self.password.len() < 8 {
+//!                 warnings.add("password", ValidationError::new("weak"));
+//!             }
+//!             if warnings.is_empty() {
+//!                 Ok(())
+//!             } else {
+//!                 Err(warnings)
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::HasValidate;
+#[cfg(feature = "validator")]
+use crate::HasValidateArgs;
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderName, HeaderValue};
+use axum::response::{IntoResponse, IntoResponseParts, Response, ResponseParts};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// Name of the response header carrying the validation warnings.
+pub const WARNINGS_HEADER: HeaderName = HeaderName::from_static("x-validation-warnings");
+
+/// Trait for data types with advisory rules, whose violations are warnings instead of errors.
+///
+pub trait Advise {
+    /// Type of the warnings
+    type Warnings;
+    /// Check the advisory rules, returning the warnings if any rule is violated
+    fn advise(&self) -> Result<(), Self::Warnings>;
+}
+
+/// Warnings of the data extracted by `E`.
+pub type WarningsOf<E> = <<E as HasValidate>::Validate as Advise>::Warnings;
+
+/// # `Warned` data extractor
+///
+/// `Warned` extracts the inner extractor `E` and checks the advisory rules of its data.
+///
+/// It never rejects because of the advisory rules, and can be used inside `Valid`, `Garde`,
+/// `Validated` etc. to also check the hard rules.
+///
+pub struct Warned<E>
+where
+    E: HasValidate,
+    E::Validate: Advise,
+{
+    /// The inner extractor
+    pub inner: E,
+    /// Warnings produced by the advisory rules, `None` if there are none
+    pub warnings: Option<WarningsOf<E>>,
+}
+
+impl<E> Debug for Warned<E>
+where
+    E: HasValidate + Debug,
+    E::Validate: Advise,
+    WarningsOf<E>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Warned")
+            .field("inner", &self.inner)
+            .field("warnings", &self.warnings)
+            .finish()
+    }
+}
+
+impl<E> Deref for Warned<E>
+where
+    E: HasValidate,
+    E::Validate: Advise,
+{
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<E> DerefMut for Warned<E>
+where
+    E: HasValidate,
+    E::Validate: Advise,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<E> Display for Warned<E>
+where
+    E: HasValidate + Display,
+    E::Validate: Advise,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<E> Warned<E>
+where
+    E: HasValidate,
+    E::Validate: Advise,
+{
+    fn new(inner: E) -> Self {
+        let warnings = inner.get_validate().advise().err();
+        Warned { inner, warnings }
+    }
+
+    /// Consumes the `Warned` and returns the inner extractor.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    /// Consumes the `Warned` and returns the inner extractor and the warnings.
+    pub fn into_parts(self) -> (E, Warnings<WarningsOf<E>>) {
+        (self.inner, Warnings(self.warnings))
+    }
+}
+
+#[cfg(feature = "aide")]
+impl<T> aide::OperationInput for Warned<T>
+where
+    T: HasValidate + aide::OperationInput,
+    T::Validate: Advise,
+{
+    fn operation_input(
+        ctx: &mut aide::generate::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) {
+        T::operation_input(ctx, operation);
+    }
+}
+
+impl<E> HasValidate for Warned<E>
+where
+    E: HasValidate,
+    E::Validate: Advise,
+{
+    type Validate = E::Validate;
+    fn get_validate(&self) -> &Self::Validate {
+        self.inner.get_validate()
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<'v, E> HasValidateArgs<'v> for Warned<E>
+where
+    E: HasValidate + HasValidateArgs<'v>,
+    E::Validate: Advise,
+{
+    type ValidateArgs = E::ValidateArgs;
+    fn get_validate_args(&self) -> &Self::ValidateArgs {
+        self.inner.get_validate_args()
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<E> crate::HasModify for Warned<E>
+where
+    E: HasValidate + crate::HasModify,
+    E::Validate: Advise,
+{
+    type Modify = E::Modify;
+
+    fn get_modify(&mut self) -> &mut Self::Modify {
+        self.inner.get_modify()
+    }
+}
+
+impl<State, Extractor> FromRequest<State> for Warned<Extractor>
+where
+    State: Send + Sync,
+    Extractor: HasValidate + FromRequest<State>,
+    Extractor::Validate: Advise,
+{
+    type Rejection = <Extractor as FromRequest<State>>::Rejection;
+
+    async fn from_request(req: Request, state: &State) -> Result<Self, Self::Rejection> {
+        Ok(Warned::new(Extractor::from_request(req, state).await?))
+    }
+}
+
+impl<State, Extractor> FromRequestParts<State> for Warned<Extractor>
+where
+    State: Send + Sync,
+    Extractor: HasValidate + FromRequestParts<State>,
+    Extractor::Validate: Advise,
+{
+    type Rejection = <Extractor as FromRequestParts<State>>::Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &State) -> Result<Self, Self::Rejection> {
+        Ok(Warned::new(
+            Extractor::from_request_parts(parts, state).await?,
+        ))
+    }
+}
+
+/// # `Warnings` response part
+///
+/// `Warnings` attaches the validation warnings, if any, to the response as the `x-validation-warnings` header.
+///
+/// The warnings are serialized into JSON format if the `into_json` feature is enabled,
+/// otherwise they are formatted using `Display`, with line breaks replaced by `; `.
+///
+#[derive(Debug, Clone, Default)]
+pub struct Warnings<W>(pub Option<W>);
+
+#[cfg(feature = "into_json")]
+impl<W: serde::Serialize> IntoResponseParts for Warnings<W> {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if let Some(value) = self
+            .0
+            .and_then(|w| serde_json::to_string(&w).ok())
+            .and_then(|w| HeaderValue::from_str(&w).ok())
+        {
+            res.headers_mut().insert(WARNINGS_HEADER, value);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(not(feature = "into_json"))]
+impl<W: Display> IntoResponseParts for Warnings<W> {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if let Some(value) = self.0.and_then(|w| {
+            let w = w.to_string();
+            let w = w
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join("; ");
+            HeaderValue::from_str(&w).ok()
+        }) {
+            res.headers_mut().insert(WARNINGS_HEADER, value);
+        }
+        Ok(res)
+    }
+}
+
+impl<W> IntoResponse for Warnings<W>
+where
+    Self: IntoResponseParts,
+{
+    fn into_response(self) -> Response {
+        (self, ()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Data {
+        password: String,
+    }
+
+    impl Advise for Data {
+        type Warnings = String;
+
+        fn advise(&self) -> Result<(), Self::Warnings> {
+            if self.password.len() < 8 {
+                Err(String::from("password is weak"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl HasValidate for Data {
+        type Validate = Data;
+
+        fn get_validate(&self) -> &Self::Validate {
+            self
+        }
+    }
+
+    #[test]
+    fn warned() {
+        let warned = Warned::new(Data {
+            password: String::from("secret"),
+        });
+        assert_eq!(warned.warnings.as_deref(), Some("password is weak"));
+        assert_eq!(warned.get_validate().password, "secret");
+        let (data, warnings) = warned.into_parts();
+        assert_eq!(data.password, "secret");
+
+        let response = warnings.into_response();
+        let header = response
+            .headers()
+            .get(WARNINGS_HEADER)
+            .expect("warnings header");
+        #[cfg(feature = "into_json")]
+        assert_eq!(header, "\"password is weak\"");
+        #[cfg(not(feature = "into_json"))]
+        assert_eq!(header, "password is weak");
+
+        let warned = Warned::new(Data {
+            password: String::from("a strong password"),
+        });
+        assert!(warned.warnings.is_none());
+        let (_, warnings) = warned.into_parts();
+        assert!(warnings
+            .into_response()
+            .headers()
+            .get(WARNINGS_HEADER)
+            .is_none());
+    }
+}
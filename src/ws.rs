@@ -0,0 +1,325 @@
+//! # Support for WebSocket messages
+//!
+//! ## Feature
+//!
+//! Enable the `ws` feature to use `ValidWebSocket<T>`, `GardeWebSocket<T>` or `ValidifyWebSocket<T>`.
+//!
+//! ## Usage
+//!
+//! 1. Implement `Deserialize` and `Validate` for your message type `T`.
+//! 2. In the `on_upgrade` callback of `WebSocketUpgrade`, wrap the `WebSocket` with `ValidWebSocket::<T>::new`
+//!    (or the alias for your validation library).
+//! 3. Receive messages with `recv`: text and binary frames are deserialized from JSON and validated.
+//!    Invalid messages are answered with an error frame and skipped, so `recv` only returns valid messages.
+//!
+//! The error frame is a text frame with the same content as the body of the HTTP rejection:
+//! the validation errors serialized into JSON if the `into_json` feature is enabled, otherwise formatted using `Display`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! #[cfg(feature = "validator")]
+//! mod validator_example {
+//!     use axum::extract::ws::{Message, WebSocketUpgrade};
+//!     use axum::response::Response;
+//!     use axum::routing::get;
+//!     use axum::Router;
+//!     use axum_valid::ws::ValidWebSocket;
+//!     use serde::Deserialize;
+//!     use validator::Validate;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/ws", get(handler))
+//!     }
+//!
+//!     async fn handler(ws: WebSocketUpgrade) -> Response {
+//!         ws.on_upgrade(|socket| async move {
+//!             let mut socket = ValidWebSocket::<Parameter>::new(socket);
+//!             while let Some(Ok(parameter)) = socket.recv().await {
+//!                 assert!(parameter.validate().is_ok());
+//!                 if socket.send(Message::Text(parameter.v1.into())).await.is_err() {
+//!                     break;
+//!                 }
+//!             }
+//!         })
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[validate(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[validate(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! #[cfg(feature = "garde")]
+//! mod garde_example {
+//!     use axum::extract::ws::WebSocketUpgrade;
+//!     use axum::response::Response;
+//!     use axum::routing::get;
+//!     use axum::Router;
+//!     use axum_valid::ws::GardeWebSocket;
+//!     use garde::Validate;
+//!     use serde::Deserialize;
+//!
+//!     pub fn router() -> Router {
+//!         Router::new().route("/ws", get(handler))
+//!     }
+//!
+//!     async fn handler(ws: WebSocketUpgrade) -> Response {
+//!         ws.on_upgrade(|socket| async move {
+//!             let mut socket = GardeWebSocket::<Parameter>::new(socket);
+//!             while let Some(Ok(parameter)) = socket.recv().await {
+//!                 assert!(parameter.validate().is_ok());
+//!             }
+//!         })
+//!     }
+//!
+//!     #[derive(Validate, Deserialize)]
+//!     pub struct Parameter {
+//!         #[garde(range(min = 5, max = 10))]
+//!         pub v0: i32,
+//!         #[garde(length(min = 1, max = 10))]
+//!         pub v1: String,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! #     use std::net::SocketAddr;
+//! #     use axum::Router;
+//! #     use tokio::net::TcpListener;
+//! #     let router = Router::new();
+//! #     #[cfg(feature = "validator")]
+//! #     let router = router.nest("/validator", validator_example::router());
+//! #     #[cfg(feature = "garde")]
+//! #     let router = router.nest("/garde", garde_example::router());
+//! #     let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+//! #     axum::serve(listener, router.into_make_service())
+//! #         .await?;
+//! #     Ok(())
+//! # }
+//! ```
+
+use axum::extract::ws::{Message, WebSocket};
+use serde::de::DeserializeOwned;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+type Check<T, V> = Box<dyn Fn(&T) -> Result<(), V> + Send + Sync>;
+
+/// # `MessageSocket` WebSocket adapter
+///
+/// `MessageSocket` wraps a `WebSocket`, deserializing and validating the incoming messages,
+/// see the [module documentation](self).
+///
+/// Use it through `ValidWebSocket<T>`, `GardeWebSocket<T>` or `ValidifyWebSocket<T>`.
+///
+pub struct MessageSocket<T, V> {
+    socket: WebSocket,
+    check: Check<T, V>,
+    render: fn(&V) -> String,
+}
+
+/// `MessageSocket` validating messages with validator.
+#[cfg(feature = "validator")]
+pub type ValidWebSocket<T> = MessageSocket<T, validator::ValidationErrors>;
+
+/// `MessageSocket` validating messages with garde.
+#[cfg(feature = "garde")]
+pub type GardeWebSocket<T> = MessageSocket<T, garde::Report>;
+
+/// `MessageSocket` validating messages with validify.
+#[cfg(feature = "validify")]
+pub type ValidifyWebSocket<T> = MessageSocket<T, validify::ValidationErrors>;
+
+#[cfg(feature = "into_json")]
+fn error_frame<V: serde::Serialize>(errors: &V) -> String {
+    serde_json::to_string(errors).unwrap_or_default()
+}
+
+#[cfg(not(feature = "into_json"))]
+fn error_frame<V: std::fmt::Display>(errors: &V) -> String {
+    errors.to_string()
+}
+
+impl<T, V> Deref for MessageSocket<T, V> {
+    type Target = WebSocket;
+
+    fn deref(&self) -> &Self::Target {
+        &self.socket
+    }
+}
+
+impl<T, V> DerefMut for MessageSocket<T, V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.socket
+    }
+}
+
+impl<T, V> Debug for MessageSocket<T, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageSocket")
+            .field("socket", &self.socket)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned, V> MessageSocket<T, V> {
+    /// Consumes the `MessageSocket` and returns the `WebSocket` within.
+    pub fn into_inner(self) -> WebSocket {
+        self.socket
+    }
+
+    /// Receive the next valid message.
+    ///
+    /// Text and binary frames are deserialized from JSON and validated. Invalid messages are answered
+    /// with an error frame and skipped, ping and pong frames are skipped.
+    ///
+    /// Returns `None` if the stream has closed or a close frame has been received.
+    pub async fn recv(&mut self) -> Option<Result<T, axum::Error>> {
+        loop {
+            let message = match self.socket.recv().await? {
+                Ok(message) => message,
+                Err(error) => return Some(Err(error)),
+            };
+            let data = match &message {
+                Message::Text(text) => serde_json::from_str::<T>(text.as_str()),
+                Message::Binary(bytes) => serde_json::from_slice::<T>(bytes),
+                Message::Close(_) => return None,
+                Message::Ping(_) | Message::Pong(_) => continue,
+            };
+            let frame = match data {
+                Ok(data) => match (self.check)(&data) {
+                    Ok(()) => return Some(Ok(data)),
+                    Err(errors) => (self.render)(&errors),
+                },
+                Err(error) => format!("Failed to deserialize the message: {error}"),
+            };
+            if let Err(error) = self.socket.send(Message::Text(frame.into())).await {
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<T> MessageSocket<T, validator::ValidationErrors>
+where
+    T: DeserializeOwned + validator::Validate,
+{
+    /// Wraps the `WebSocket`, validating messages with validator.
+    pub fn new(socket: WebSocket) -> Self {
+        MessageSocket {
+            socket,
+            check: Box::new(|data: &T| data.validate()),
+            render: error_frame,
+        }
+    }
+}
+
+#[cfg(feature = "garde")]
+impl<T> MessageSocket<T, garde::Report>
+where
+    T: DeserializeOwned + garde::Validate,
+    T::Context: Send + Sync + 'static,
+{
+    /// Wraps the `WebSocket`, validating messages with garde and the default context.
+    pub fn new(socket: WebSocket) -> Self
+    where
+        T::Context: Default,
+    {
+        Self::with_context(socket, Default::default())
+    }
+
+    /// Wraps the `WebSocket`, validating messages with garde and the given context.
+    pub fn with_context(socket: WebSocket, context: T::Context) -> Self {
+        MessageSocket {
+            socket,
+            check: Box::new(move |data: &T| data.validate_with(&context)),
+            render: error_frame,
+        }
+    }
+}
+
+#[cfg(feature = "validify")]
+impl<T> MessageSocket<T, validify::ValidationErrors>
+where
+    T: DeserializeOwned + validify::Validate,
+{
+    /// Wraps the `WebSocket`, validating messages with validify.
+    pub fn new(socket: WebSocket) -> Self {
+        MessageSocket {
+            socket,
+            check: Box::new(|data: &T| data.validate()),
+            render: error_frame,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "validator"))]
+mod tests {
+    use super::*;
+    use axum::extract::WebSocketUpgrade;
+    use axum::response::Response;
+    use axum::routing::get;
+    use axum::Router;
+    use futures_util::{SinkExt, StreamExt};
+    use std::net::SocketAddr;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite;
+    use validator::Validate;
+
+    #[derive(Debug, serde::Deserialize, Validate)]
+    struct Data {
+        #[validate(range(min = 5, max = 10))]
+        v0: i32,
+    }
+
+    async fn handler(ws: WebSocketUpgrade) -> Response {
+        ws.on_upgrade(|socket| async move {
+            let mut socket = ValidWebSocket::<Data>::new(socket);
+            while let Some(Ok(data)) = socket.recv().await {
+                let echo = format!("ok {}", data.v0);
+                if socket.send(Message::Text(echo.into())).await.is_err() {
+                    break;
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn valid_web_socket() -> anyhow::Result<()> {
+        let router = Router::new().route("/ws", get(handler));
+        let listener = TcpListener::bind(&SocketAddr::from(([127u8, 0, 0, 1], 0u16))).await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws")).await?;
+
+        let mut exchange = async |message: tungstenite::Message| -> anyhow::Result<String> {
+            client.send(message).await?;
+            match client.next().await {
+                Some(Ok(tungstenite::Message::Text(text))) => Ok(text.to_string()),
+                other => anyhow::bail!("unexpected frame: {other:?}"),
+            }
+        };
+
+        let reply = exchange(tungstenite::Message::text(r#"{"v0":5}"#)).await?;
+        assert_eq!(reply, "ok 5");
+
+        let reply = exchange(tungstenite::Message::text(r#"{"v0":11}"#)).await?;
+        #[cfg(feature = "into_json")]
+        assert!(serde_json::from_str::<serde_json::Value>(&reply)?["v0"].is_array());
+        #[cfg(not(feature = "into_json"))]
+        assert!(reply.starts_with("v0: Validation error: range"));
+
+        let reply = exchange(tungstenite::Message::text("{")).await?;
+        assert!(reply.starts_with("Failed to deserialize the message"));
+
+        let reply = exchange(tungstenite::Message::binary(r#"{"v0":10}"#)).await?;
+        assert_eq!(reply, "ok 10");
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+//! # Derived extractor traits
+//!
+
+#![cfg(all(feature = "derive", feature = "json"))]
+
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use axum_valid::{HasValidate, VALIDATION_ERROR_STATUS};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+
+// 1. Define a custom extractor wrapping some data, and derive traits for it.
+//    The field holding the data is marked with `#[axum_valid(inner)]`.
+#[derive(HasValidate)]
+#[cfg_attr(feature = "validator", derive(axum_valid::HasValidateArgs))]
+#[cfg_attr(
+    feature = "validify",
+    derive(
+        axum_valid::HasModify,
+        axum_valid::PayloadExtractor,
+        axum_valid::HasValidify
+    )
+)]
+struct Body<T> {
+    #[axum_valid(inner)]
+    data: T,
+    length: usize,
+}
+
+impl<S, T> FromRequest<S> for Body<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = <Json<T> as FromRequest<S>>::Rejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let length = req
+            .headers()
+            .get(axum::http::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default();
+        let Json(data) = Json::<T>::from_request(req, state).await?;
+        Ok(Body { data, length })
+    }
+}
+
+// 2. Newtype extractors don't need the attribute.
+#[derive(HasValidate)]
+struct Wrapper<T>(T);
+
+impl<S, T> FromRequest<S> for Wrapper<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = <Json<T> as FromRequest<S>>::Rejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(data) = Json::<T>::from_request(req, state).await?;
+        Ok(Wrapper(data))
+    }
+}
+
+#[derive(Serialize)]
+struct Content {
+    content: &'static str,
+}
+
+async fn check(router: Router) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+    let server_addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router.into_make_service()).await;
+    });
+
+    let client = reqwest::Client::default();
+    let url = format!("http://{}/", server_addr);
+
+    let valid = Content { content: "hello" };
+    let response = client.post(&url).json(&valid).send().await?;
+    assert_eq!(response.status().as_u16(), StatusCode::OK.as_u16());
+
+    let invalid = Content { content: "" };
+    let response = client.post(&url).json(&invalid).send().await?;
+    assert_eq!(response.status().as_u16(), VALIDATION_ERROR_STATUS.as_u16());
+    Ok(())
+}
+
+#[cfg(feature = "validator")]
+#[tokio::test]
+async fn derive_validator() -> anyhow::Result<()> {
+    use axum_valid::{Valid, ValidEx};
+    use validator::{Validate, ValidationError};
+
+    #[derive(Deserialize, Validate)]
+    struct Data {
+        #[validate(length(min = 1, max = 10))]
+        content: String,
+    }
+
+    #[derive(Deserialize, Validate)]
+    #[validate(context = MaxLength)]
+    struct DataEx {
+        #[validate(custom(function = "validate_content", use_context))]
+        content: String,
+    }
+
+    #[derive(Clone)]
+    struct MaxLength(usize);
+
+    fn validate_content(v: &str, args: &MaxLength) -> Result<(), ValidationError> {
+        (!v.is_empty() && v.len() <= args.0)
+            .then_some(())
+            .ok_or_else(|| ValidationError::new("content"))
+    }
+
+    async fn body(Valid(body): Valid<Body<Data>>) -> StatusCode {
+        assert!(body.length > 0);
+        StatusCode::OK
+    }
+    check(Router::new().route("/", post(body))).await?;
+
+    async fn wrapper(Valid(Wrapper(data)): Valid<Wrapper<Data>>) -> StatusCode {
+        assert!(!data.content.is_empty());
+        StatusCode::OK
+    }
+    check(Router::new().route("/", post(wrapper))).await?;
+
+    async fn body_ex(ValidEx(_): ValidEx<Body<DataEx>>) -> StatusCode {
+        StatusCode::OK
+    }
+    check(
+        Router::new()
+            .route("/", post(body_ex))
+            .with_state(MaxLength(10)),
+    )
+    .await?;
+    Ok(())
+}
+
+#[cfg(feature = "garde")]
+#[tokio::test]
+async fn derive_garde() -> anyhow::Result<()> {
+    use axum_valid::Garde;
+    use garde::Validate;
+
+    #[derive(Deserialize, Validate)]
+    struct Data {
+        #[garde(length(min = 1, max = 10))]
+        content: String,
+    }
+
+    async fn body(Garde(body): Garde<Body<Data>>) -> StatusCode {
+        assert!(body.length > 0);
+        StatusCode::OK
+    }
+    check(Router::new().route("/", post(body))).await
+}
+
+#[cfg(feature = "validify")]
+#[tokio::test]
+async fn derive_validify() -> anyhow::Result<()> {
+    use axum_valid::{Modified, Validated, Validified, ValidifiedByRef};
+    use validify::{Payload, Validify};
+
+    #[derive(Deserialize, Validify, Payload)]
+    struct Data {
+        #[modify(trim)]
+        #[validate(length(min = 1, max = 10))]
+        content: String,
+    }
+
+    async fn validated(Validated(_): Validated<Body<Data>>) -> StatusCode {
+        StatusCode::OK
+    }
+    check(Router::new().route("/", post(validated))).await?;
+
+    async fn validified(Validified(body): Validified<Body<Data>>) -> StatusCode {
+        assert_eq!(body.data.content, body.data.content.trim());
+        StatusCode::OK
+    }
+    check(Router::new().route("/", post(validified))).await?;
+
+    async fn validified_by_ref(ValidifiedByRef(body): ValidifiedByRef<Body<Data>>) -> StatusCode {
+        assert_eq!(body.data.content, body.data.content.trim());
+        StatusCode::OK
+    }
+    check(Router::new().route("/", post(validified_by_ref))).await?;
+
+    let listener = TcpListener::bind(&SocketAddr::from(([0u8, 0, 0, 0], 0u16))).await?;
+    let server_addr = listener.local_addr()?;
+    async fn modified(Modified(body): Modified<Body<Data>>) -> String {
+        body.data.content
+    }
+    tokio::spawn(async move {
+        let router = Router::new().route("/", post(modified));
+        let _ = axum::serve(listener, router.into_make_service()).await;
+    });
+    let content = reqwest::Client::default()
+        .post(format!("http://{}/", server_addr))
+        .json(&Content {
+            content: "  hello  ",
+        })
+        .send()
+        .await?
+        .text()
+        .await?;
+    assert_eq!(content, "hello");
+    Ok(())
+}
+
+#[cfg(feature = "normalize")]
+#[test]
+fn derive_normalize() {
+    use axum_valid::normalize::Normalize;
+
+    #[derive(Normalize)]
+    struct Name(#[normalize(strip_control, collapse_whitespace, nfc)] String);
+
+    #[derive(Normalize)]
+    struct User<T> {
+        #[normalize(trim, lowercase)]
+        email: String,
+        #[normalize(trim)]
+        #[normalize(lowercase)]
+        tags: Vec<Option<String>>,
+        #[normalize(nested)]
+        name: Option<Name>,
+        #[normalize(nested)]
+        extra: T,
+        raw: String,
+    }
+
+    let mut user = User {
+        email: String::from("  John@Example.COM "),
+        tags: vec![Some(String::from(" Rust ")), None],
+        name: Some(Name(String::from(" John\t\u{0} Doe\u{301} "))),
+        extra: vec![Name(String::from("a  b"))],
+        raw: String::from(" Raw "),
+    };
+    user.normalize();
+    assert_eq!(user.email, "john@example.com");
+    assert_eq!(user.tags, [Some(String::from("rust")), None]);
+    assert_eq!(user.name.map(|n| n.0).as_deref(), Some("John Do\u{e9}"));
+    assert_eq!(user.extra[0].0, "a b");
+    assert_eq!(user.raw, " Raw ");
+}
+
+#[cfg(feature = "sanitize")]
+#[test]
+fn derive_normalize_sanitize() {
+    use axum_valid::normalize::Normalize;
+
+    #[derive(Normalize)]
+    struct Profile {
+        #[normalize(sanitize_html, trim)]
+        bio: String,
+        #[normalize(strip_html)]
+        nickname: Option<String>,
+    }
+
+    let mut profile = Profile {
+        bio: String::from("<em>Hi</em><img src=x onerror=alert(1)> "),
+        nickname: Some(String::from("<b>Bob</b>")),
+    };
+    profile.normalize();
+    assert_eq!(profile.bio, r#"<em>Hi</em><img src="x">"#);
+    assert_eq!(profile.nickname.as_deref(), Some("Bob"));
+}